@@ -0,0 +1,97 @@
+//! Constants describing the binary layout.
+//!
+//! All integers are little-endian. The file begins with a header of eight
+//! fields, followed by these sections with no padding between them:
+//!
+//! | Section                 | Entries | Entry                            |
+//! | ----------------------- | ------- | -------------------------------- |
+//! | Radicals                | R       | `u32` code point                 |
+//! | Decomposition kanji     | D       | `u32` code point                 |
+//! | Decomposition offsets   | D + 1   | `u32` index into components      |
+//! | Components              | C       | `u16` radical id                 |
+//! | Membership records      | M       | [`MEMBERSHIP_SIZE`] bytes        |
+//! | Membership offsets      | M + 1   | `u32` index into members         |
+//! | Members                 | K       | `u32` code point                 |
+//! | Strings                 | S       | UTF-8 byte                       |
+//!
+//! A radical id is an index into the radicals section. Each membership record
+//! holds a `u16` radical id, a `u8` stroke count, a `u8` alternate kind, and
+//! the `u32` start and `u32` length of the alternate within the strings.
+
+/// Identifies the start of the file
+pub const MAGIC: [u8; 4] = *b"KRDB";
+
+/// The version of the layout described here
+pub const VERSION: u16 = 1;
+
+/// The size of the header in bytes
+pub const HEADER_SIZE: usize = 32;
+
+/// The size of a membership record in bytes
+pub const MEMBERSHIP_SIZE: usize = 12;
+
+/// The alternate kind for a radical without an alternate
+pub const ALTERNATE_NONE: u8 = 0;
+
+/// The alternate kind for the name of an image
+pub const ALTERNATE_IMAGE: u8 = 1;
+
+/// The alternate kind for another glyph
+pub const ALTERNATE_GLYPH: u8 = 2;
+
+/// The counts stored in the header after the magic and version
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Header {
+    /// The number of radicals, R
+    pub radicals: u32,
+
+    /// The number of decompositions, D
+    pub decompositions: u32,
+
+    /// The number of radicals across all decompositions, C
+    pub components: u32,
+
+    /// The number of memberships, M
+    pub memberships: u32,
+
+    /// The number of kanji across all memberships, K
+    pub members: u32,
+
+    /// The length of the strings section in bytes, S
+    pub strings: u32,
+}
+
+impl Header {
+    /// Writes the header in its binary form
+    pub fn to_bytes(&self) -> [u8; HEADER_SIZE] {
+        let mut bytes = [0; HEADER_SIZE];
+        bytes[0..4].copy_from_slice(&MAGIC);
+        bytes[4..6].copy_from_slice(&VERSION.to_le_bytes());
+        let counts = [
+            self.radicals,
+            self.decompositions,
+            self.components,
+            self.memberships,
+            self.members,
+            self.strings,
+        ];
+        for (i, count) in counts.iter().enumerate() {
+            let start = 8 + i * 4;
+            bytes[start..start + 4].copy_from_slice(&count.to_le_bytes());
+        }
+        bytes
+    }
+
+    /// The total size in bytes of a file with this header
+    pub fn file_size(&self) -> usize {
+        HEADER_SIZE
+            + self.radicals as usize * 4
+            + self.decompositions as usize * 8
+            + 4
+            + self.components as usize * 2
+            + self.memberships as usize * (MEMBERSHIP_SIZE + 4)
+            + 4
+            + self.members as usize * 4
+            + self.strings as usize
+    }
+}
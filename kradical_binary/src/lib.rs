@@ -0,0 +1,446 @@
+//! Reads the compact binary form of the `kradfile`s and `radkfile`s written by
+//! `kradical_converter`. The data is read in place without copying, so it can
+//! come from `include_bytes!` or a memory-mapped file.
+//!
+//! ```no_run
+//! let bytes = std::fs::read("kradical.bin").unwrap();
+//! let data = kradical_binary::Data::new(&bytes).unwrap();
+//! for decomposition in data.decompositions() {
+//!     let radicals: String = decomposition.radicals.collect();
+//!     println!("{} : {}", decomposition.kanji, radicals);
+//! }
+//! ```
+
+pub mod layout;
+mod writer;
+
+#[cfg(test)]
+mod tests;
+
+use layout::{Header, ALTERNATE_GLYPH, ALTERNATE_IMAGE, ALTERNATE_NONE, HEADER_SIZE};
+use std::iter::FusedIterator;
+use thiserror::Error;
+
+pub use writer::Writer;
+
+#[doc = include_str!("../README.md")]
+#[cfg(doctest)]
+struct ReadmeDoctests;
+
+/// Enumerates the crate's possible errors
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+pub enum BinaryError {
+    /// The data does not start with the magic number
+    #[error("The data is not in the kradical binary format")]
+    Magic,
+
+    /// The data uses a layout this version of the crate cannot read
+    #[error("Unsupported binary format version {0}")]
+    Version(u16),
+
+    /// The data is shorter or longer than its header describes
+    #[error("Expected {expected} bytes of data but found {found}")]
+    Length { expected: usize, found: usize },
+
+    /// A section contains an out-of-range value
+    #[error("Invalid contents in the {0} section")]
+    Invalid(&'static str),
+
+    /// There are more distinct radicals than fit in the 16-bit radical ids
+    #[error("Expected at most 65536 distinct radicals")]
+    TooManyRadicals,
+}
+
+/// The decompositions and memberships stored in a binary file
+#[derive(Debug, Clone, Copy)]
+pub struct Data<'a> {
+    radicals: &'a [u8],
+    decomposition_kanji: &'a [u8],
+    decomposition_offsets: &'a [u8],
+    components: &'a [u8],
+    membership_records: &'a [u8],
+    membership_offsets: &'a [u8],
+    members: &'a [u8],
+    strings: &'a str,
+}
+
+/// The constituent radicals for a kanji
+#[derive(Debug, Clone)]
+pub struct Decomposition<'a> {
+    /// The kanji
+    pub kanji: char,
+
+    /// The radicals contained in the kanji
+    pub radicals: Radicals<'a>,
+}
+
+/// The kanji that contain a radical
+#[derive(Debug, Clone)]
+pub struct Membership<'a> {
+    /// The radical
+    pub radical: char,
+
+    /// The kanjis that contain the radical
+    pub kanji: Kanji<'a>,
+
+    /// The number of strokes to draw the radical
+    pub strokes: u8,
+
+    /// An alternate representation of the radical
+    pub alternate: Alternate<'a>,
+}
+
+/// Alternate representations for a radical other than the UTF-8 glyph
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Alternate<'a> {
+    /// The name of an image from the WWWJDIC website
+    Image(&'a str),
+
+    /// Another glyph that better depicts the radical
+    Glyph(char),
+
+    /// No alternate representation provided
+    None,
+}
+
+impl<'a> Data<'a> {
+    /// Checks the layout of the data and prepares it for reading
+    pub fn new(bytes: &'a [u8]) -> Result<Self, BinaryError> {
+        let header = read_header(bytes)?;
+        let expected = header.file_size();
+        if bytes.len() != expected {
+            return Err(BinaryError::Length {
+                expected,
+                found: bytes.len(),
+            });
+        }
+
+        let mut rest = &bytes[HEADER_SIZE..];
+        let mut take = |len: usize| {
+            let (section, remainder) = rest.split_at(len);
+            rest = remainder;
+            section
+        };
+        let radicals = take(header.radicals as usize * 4);
+        let decomposition_kanji = take(header.decompositions as usize * 4);
+        let decomposition_offsets = take((header.decompositions as usize + 1) * 4);
+        let components = take(header.components as usize * 2);
+        let membership_records = take(header.memberships as usize * layout::MEMBERSHIP_SIZE);
+        let membership_offsets = take((header.memberships as usize + 1) * 4);
+        let members = take(header.members as usize * 4);
+        let strings = std::str::from_utf8(take(header.strings as usize))
+            .map_err(|_| BinaryError::Invalid("strings"))?;
+
+        let data = Self {
+            radicals,
+            decomposition_kanji,
+            decomposition_offsets,
+            components,
+            membership_records,
+            membership_offsets,
+            members,
+            strings,
+        };
+        data.validate(&header)?;
+        Ok(data)
+    }
+
+    /// The list of radical decompositions from the `kradfile`
+    pub fn decompositions(&self) -> Decompositions<'a> {
+        Decompositions {
+            data: *self,
+            range: 0..self.decomposition_kanji.len() / 4,
+        }
+    }
+
+    /// For each radical, a list of which kanji contain it from the `radkfile`
+    pub fn memberships(&self) -> Memberships<'a> {
+        Memberships {
+            data: *self,
+            range: 0..self.membership_records.len() / layout::MEMBERSHIP_SIZE,
+        }
+    }
+
+    fn decomposition(&self, index: usize) -> Decomposition<'a> {
+        let (start, end) = span(self.decomposition_offsets, index);
+        Decomposition {
+            kanji: code_point(self.decomposition_kanji, index),
+            radicals: Radicals {
+                ids: &self.components[start * 2..end * 2],
+                radicals: self.radicals,
+            },
+        }
+    }
+
+    fn membership(&self, index: usize) -> Membership<'a> {
+        let record = self.record(index);
+        let (start, end) = span(self.membership_offsets, index);
+        Membership {
+            radical: code_point(self.radicals, record.radical),
+            kanji: Kanji {
+                code_points: &self.members[start * 4..end * 4],
+            },
+            strokes: record.strokes,
+            alternate: match record.alternate_kind {
+                ALTERNATE_IMAGE => Alternate::Image(record.alternate),
+                ALTERNATE_GLYPH => record
+                    .alternate
+                    .chars()
+                    .next()
+                    .map_or(Alternate::None, Alternate::Glyph),
+                _ => Alternate::None,
+            },
+        }
+    }
+
+    fn record(&self, index: usize) -> Record<'a> {
+        let start = index * layout::MEMBERSHIP_SIZE;
+        let bytes = &self.membership_records[start..start + layout::MEMBERSHIP_SIZE];
+        let alternate_start = u32_at(bytes, 4) as usize;
+        let alternate_len = u32_at(bytes, 8) as usize;
+        Record {
+            radical: u16_at(bytes, 0) as usize,
+            strokes: bytes[2],
+            alternate_kind: bytes[3],
+            alternate: self
+                .strings
+                .get(alternate_start..alternate_start + alternate_len)
+                .unwrap_or_default(),
+        }
+    }
+
+    fn validate(&self, header: &Header) -> Result<(), BinaryError> {
+        let radical_count = header.radicals as usize;
+        check_code_points(self.radicals, "radicals")?;
+        check_code_points(self.decomposition_kanji, "decomposition kanji")?;
+        check_offsets(
+            self.decomposition_offsets,
+            header.components,
+            "decomposition offsets",
+        )?;
+        if (0..self.components.len() / 2)
+            .any(|i| u16_at(self.components, i * 2) as usize >= radical_count)
+        {
+            return Err(BinaryError::Invalid("components"));
+        }
+        for index in 0..header.memberships as usize {
+            let start = index * layout::MEMBERSHIP_SIZE;
+            let bytes = &self.membership_records[start..start + layout::MEMBERSHIP_SIZE];
+            let alternate_start = u32_at(bytes, 4) as usize;
+            let alternate = alternate_start
+                .checked_add(u32_at(bytes, 8) as usize)
+                .and_then(|end| self.strings.get(alternate_start..end));
+            let valid = (u16_at(bytes, 0) as usize) < radical_count
+                && match (bytes[3], alternate) {
+                    (ALTERNATE_NONE, Some("")) => true,
+                    (ALTERNATE_IMAGE, Some(_)) => true,
+                    (ALTERNATE_GLYPH, Some(glyph)) => glyph.chars().count() == 1,
+                    _ => false,
+                };
+            if !valid {
+                return Err(BinaryError::Invalid("membership records"));
+            }
+        }
+        check_offsets(
+            self.membership_offsets,
+            header.members,
+            "membership offsets",
+        )?;
+        check_code_points(self.members, "members")
+    }
+}
+
+/// A membership record before its alternate is interpreted
+struct Record<'a> {
+    radical: usize,
+    strokes: u8,
+    alternate_kind: u8,
+    alternate: &'a str,
+}
+
+/// Iterates over the decompositions in a binary file
+#[derive(Debug, Clone)]
+pub struct Decompositions<'a> {
+    data: Data<'a>,
+    range: std::ops::Range<usize>,
+}
+
+impl<'a> Iterator for Decompositions<'a> {
+    type Item = Decomposition<'a>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.range.next().map(|i| self.data.decomposition(i))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.range.size_hint()
+    }
+}
+
+impl<'a> DoubleEndedIterator for Decompositions<'a> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.range.next_back().map(|i| self.data.decomposition(i))
+    }
+}
+
+impl<'a> ExactSizeIterator for Decompositions<'a> {}
+impl<'a> FusedIterator for Decompositions<'a> {}
+
+/// Iterates over the memberships in a binary file
+#[derive(Debug, Clone)]
+pub struct Memberships<'a> {
+    data: Data<'a>,
+    range: std::ops::Range<usize>,
+}
+
+impl<'a> Iterator for Memberships<'a> {
+    type Item = Membership<'a>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.range.next().map(|i| self.data.membership(i))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.range.size_hint()
+    }
+}
+
+impl<'a> DoubleEndedIterator for Memberships<'a> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.range.next_back().map(|i| self.data.membership(i))
+    }
+}
+
+impl<'a> ExactSizeIterator for Memberships<'a> {}
+impl<'a> FusedIterator for Memberships<'a> {}
+
+/// Iterates over the radicals of a decomposition
+#[derive(Debug, Clone)]
+pub struct Radicals<'a> {
+    ids: &'a [u8],
+    radicals: &'a [u8],
+}
+
+impl<'a> Iterator for Radicals<'a> {
+    type Item = char;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.ids.is_empty() {
+            return None;
+        }
+        let id = u16_at(self.ids, 0) as usize;
+        self.ids = &self.ids[2..];
+        Some(code_point(self.radicals, id))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.ids.len() / 2;
+        (len, Some(len))
+    }
+}
+
+impl<'a> ExactSizeIterator for Radicals<'a> {}
+impl<'a> FusedIterator for Radicals<'a> {}
+
+/// Iterates over the kanji of a membership
+#[derive(Debug, Clone)]
+pub struct Kanji<'a> {
+    code_points: &'a [u8],
+}
+
+impl<'a> Iterator for Kanji<'a> {
+    type Item = char;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.code_points.is_empty() {
+            return None;
+        }
+        let kanji = code_point(self.code_points, 0);
+        self.code_points = &self.code_points[4..];
+        Some(kanji)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.code_points.len() / 4;
+        (len, Some(len))
+    }
+}
+
+impl<'a> ExactSizeIterator for Kanji<'a> {}
+impl<'a> FusedIterator for Kanji<'a> {}
+
+fn read_header(bytes: &[u8]) -> Result<Header, BinaryError> {
+    if bytes.len() < HEADER_SIZE {
+        return Err(BinaryError::Length {
+            expected: HEADER_SIZE,
+            found: bytes.len(),
+        });
+    }
+    if bytes[0..4] != layout::MAGIC {
+        return Err(BinaryError::Magic);
+    }
+    let version = u16_at(bytes, 4);
+    if version != layout::VERSION {
+        return Err(BinaryError::Version(version));
+    }
+    Ok(Header {
+        radicals: u32_at(bytes, 8),
+        decompositions: u32_at(bytes, 12),
+        components: u32_at(bytes, 16),
+        memberships: u32_at(bytes, 20),
+        members: u32_at(bytes, 24),
+        strings: u32_at(bytes, 28),
+    })
+}
+
+fn check_code_points(bytes: &[u8], section: &'static str) -> Result<(), BinaryError> {
+    if (0..bytes.len() / 4).all(|i| char::from_u32(u32_at(bytes, i * 4)).is_some()) {
+        Ok(())
+    } else {
+        Err(BinaryError::Invalid(section))
+    }
+}
+
+/// Offsets must start at zero, never decrease, and end at the section length
+fn check_offsets(bytes: &[u8], len: u32, section: &'static str) -> Result<(), BinaryError> {
+    let count = bytes.len() / 4;
+    let mut previous = 0;
+    for i in 0..count {
+        let offset = u32_at(bytes, i * 4);
+        if (i == 0 && offset != 0) || offset < previous {
+            return Err(BinaryError::Invalid(section));
+        }
+        previous = offset;
+    }
+    if previous == len {
+        Ok(())
+    } else {
+        Err(BinaryError::Invalid(section))
+    }
+}
+
+/// The start and end of an entry's slice in a section indexed by offsets
+fn span(offsets: &[u8], index: usize) -> (usize, usize) {
+    (
+        u32_at(offsets, index * 4) as usize,
+        u32_at(offsets, index * 4 + 4) as usize,
+    )
+}
+
+/// The code points are validated when the data is loaded
+fn code_point(bytes: &[u8], index: usize) -> char {
+    char::from_u32(u32_at(bytes, index * 4)).unwrap_or(char::REPLACEMENT_CHARACTER)
+}
+
+fn u16_at(bytes: &[u8], start: usize) -> u16 {
+    u16::from_le_bytes([bytes[start], bytes[start + 1]])
+}
+
+fn u32_at(bytes: &[u8], start: usize) -> u32 {
+    u32::from_le_bytes([
+        bytes[start],
+        bytes[start + 1],
+        bytes[start + 2],
+        bytes[start + 3],
+    ])
+}
@@ -0,0 +1,103 @@
+use super::*;
+
+fn sample() -> Vec<u8> {
+    let mut writer = Writer::new();
+    writer.decomposition('亜', &['｜', '一', '口']).unwrap();
+    writer.decomposition('悒', &['心', '邑']).unwrap();
+    writer
+        .membership('一', 1, Alternate::None, &['亜', '丁'])
+        .unwrap();
+    writer
+        .membership('⻌', 3, Alternate::Glyph('辶'), &['込'])
+        .unwrap();
+    writer
+        .membership('化', 2, Alternate::Image("js01"), &['化', '花'])
+        .unwrap();
+    writer.finish()
+}
+
+#[test]
+fn reads_decompositions() {
+    let bytes = sample();
+    let data = Data::new(&bytes).unwrap();
+    let decompositions: Vec<_> = data
+        .decompositions()
+        .map(|d| (d.kanji, d.radicals.collect::<String>()))
+        .collect();
+    assert_eq!(
+        decompositions,
+        vec![('亜', "｜一口".to_string()), ('悒', "心邑".to_string())]
+    );
+}
+
+#[test]
+fn reads_memberships() {
+    let bytes = sample();
+    let data = Data::new(&bytes).unwrap();
+    let memberships: Vec<_> = data
+        .memberships()
+        .map(|m| {
+            (
+                m.radical,
+                m.strokes,
+                m.alternate,
+                m.kanji.collect::<String>(),
+            )
+        })
+        .collect();
+    assert_eq!(
+        memberships,
+        vec![
+            ('一', 1, Alternate::None, "亜丁".to_string()),
+            ('⻌', 3, Alternate::Glyph('辶'), "込".to_string()),
+            ('化', 2, Alternate::Image("js01"), "化花".to_string()),
+        ]
+    );
+    assert_eq!(data.memberships().len(), 3);
+}
+
+#[test]
+fn rejects_wrong_magic() {
+    let mut bytes = sample();
+    bytes[0] = b'X';
+    assert_eq!(Data::new(&bytes).unwrap_err(), BinaryError::Magic);
+}
+
+#[test]
+fn rejects_truncated_data() {
+    let bytes = sample();
+    let res = Data::new(&bytes[..bytes.len() - 1]);
+    assert_eq!(
+        res.unwrap_err(),
+        BinaryError::Length {
+            expected: bytes.len(),
+            found: bytes.len() - 1
+        }
+    );
+}
+
+#[test]
+fn rejects_out_of_range_radical() {
+    let mut bytes = sample();
+    // The first component follows the header, seven radicals,
+    // two kanji, and three offsets
+    let start = layout::HEADER_SIZE + 7 * 4 + 2 * 4 + 3 * 4;
+    bytes[start..start + 2].copy_from_slice(&100u16.to_le_bytes());
+    assert_eq!(
+        Data::new(&bytes).unwrap_err(),
+        BinaryError::Invalid("components")
+    );
+}
+
+#[test]
+fn rejects_too_many_radicals() {
+    let radicals: Vec<_> = (0x10000..=0x20000)
+        .filter_map(std::char::from_u32)
+        .collect();
+    let mut writer = Writer::new();
+    assert_eq!(writer.decomposition('亜', &radicals[..65536]), Ok(()));
+    assert_eq!(
+        writer.decomposition('唖', &radicals[65536..]),
+        Err(BinaryError::TooManyRadicals)
+    );
+}
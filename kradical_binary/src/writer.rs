@@ -0,0 +1,119 @@
+use crate::{
+    layout::{Header, ALTERNATE_GLYPH, ALTERNATE_IMAGE, ALTERNATE_NONE},
+    Alternate, BinaryError,
+};
+use std::collections::HashMap;
+use std::convert::TryFrom;
+
+/// Collects decompositions and memberships and writes them in the binary format
+#[derive(Debug, Default)]
+pub struct Writer {
+    radicals: Vec<char>,
+    radical_ids: HashMap<char, u16>,
+    decomposition_kanji: Vec<char>,
+    decomposition_offsets: Vec<u32>,
+    components: Vec<u16>,
+    membership_records: Vec<(u16, u8, u8, u32, u32)>,
+    membership_offsets: Vec<u32>,
+    members: Vec<char>,
+    strings: String,
+}
+
+impl Writer {
+    pub fn new() -> Self {
+        Self {
+            decomposition_offsets: vec![0],
+            membership_offsets: vec![0],
+            ..Self::default()
+        }
+    }
+
+    /// Adds the radicals for a kanji
+    pub fn decomposition(&mut self, kanji: char, radicals: &[char]) -> Result<(), BinaryError> {
+        for &radical in radicals {
+            let id = self.radical_id(radical)?;
+            self.components.push(id);
+        }
+        self.decomposition_kanji.push(kanji);
+        self.decomposition_offsets
+            .push(self.components.len() as u32);
+        Ok(())
+    }
+
+    /// Adds the kanji containing a radical
+    pub fn membership(
+        &mut self,
+        radical: char,
+        strokes: u8,
+        alternate: Alternate<'_>,
+        kanji: &[char],
+    ) -> Result<(), BinaryError> {
+        let id = self.radical_id(radical)?;
+        let (kind, text) = match alternate {
+            Alternate::Image(image) => (ALTERNATE_IMAGE, image.to_string()),
+            Alternate::Glyph(glyph) => (ALTERNATE_GLYPH, glyph.to_string()),
+            Alternate::None => (ALTERNATE_NONE, String::new()),
+        };
+        let start = self.strings.len() as u32;
+        self.strings.push_str(&text);
+        self.membership_records
+            .push((id, strokes, kind, start, text.len() as u32));
+        self.members.extend_from_slice(kanji);
+        self.membership_offsets.push(self.members.len() as u32);
+        Ok(())
+    }
+
+    /// Writes everything added so far
+    pub fn finish(&self) -> Vec<u8> {
+        let header = Header {
+            radicals: self.radicals.len() as u32,
+            decompositions: self.decomposition_kanji.len() as u32,
+            components: self.components.len() as u32,
+            memberships: self.membership_records.len() as u32,
+            members: self.members.len() as u32,
+            strings: self.strings.len() as u32,
+        };
+        let mut bytes = Vec::with_capacity(header.file_size());
+        bytes.extend_from_slice(&header.to_bytes());
+        extend_chars(&mut bytes, &self.radicals);
+        extend_chars(&mut bytes, &self.decomposition_kanji);
+        extend_u32s(&mut bytes, &self.decomposition_offsets);
+        for id in self.components.iter() {
+            bytes.extend_from_slice(&id.to_le_bytes());
+        }
+        for &(radical, strokes, kind, start, len) in self.membership_records.iter() {
+            bytes.extend_from_slice(&radical.to_le_bytes());
+            bytes.push(strokes);
+            bytes.push(kind);
+            bytes.extend_from_slice(&start.to_le_bytes());
+            bytes.extend_from_slice(&len.to_le_bytes());
+        }
+        extend_u32s(&mut bytes, &self.membership_offsets);
+        extend_chars(&mut bytes, &self.members);
+        bytes.extend_from_slice(self.strings.as_bytes());
+        bytes
+    }
+
+    /// The id of a radical, assigning the next one if it is new
+    fn radical_id(&mut self, radical: char) -> Result<u16, BinaryError> {
+        if let Some(&id) = self.radical_ids.get(&radical) {
+            return Ok(id);
+        }
+        let id = u16::try_from(self.radicals.len()).map_err(|_| BinaryError::TooManyRadicals)?;
+        self.radicals.push(radical);
+        self.radical_ids.insert(radical, id);
+        Ok(id)
+    }
+}
+
+fn extend_chars(bytes: &mut Vec<u8>, chars: &[char]) {
+    for &c in chars {
+        bytes.extend_from_slice(&(c as u32).to_le_bytes());
+    }
+}
+
+fn extend_u32s(bytes: &mut Vec<u8>, values: &[u32]) {
+    for value in values {
+        bytes.extend_from_slice(&value.to_le_bytes());
+    }
+}
@@ -0,0 +1,79 @@
+use crate::error::FormatError;
+use kradical_binary::{Alternate, Writer};
+use kradical_parsing::{krad::Decomposition, radk};
+
+pub fn from_decompositions(decompositions: &[Decomposition]) -> Result<Vec<u8>, FormatError> {
+    from_dataset(decompositions, &[])
+}
+
+pub fn from_memberships(memberships: &[radk::Membership]) -> Result<Vec<u8>, FormatError> {
+    from_dataset(&[], memberships)
+}
+
+pub fn from_dataset(
+    decompositions: &[Decomposition],
+    memberships: &[radk::Membership],
+) -> Result<Vec<u8>, FormatError> {
+    let mut writer = Writer::new();
+    for decomposition in decompositions {
+        writer.decomposition(
+            single_char(&decomposition.kanji)?,
+            &chars(&decomposition.radicals)?,
+        )?;
+    }
+    for membership in memberships {
+        let radical = &membership.radical;
+        let alternate = match &radical.alternate {
+            radk::Alternate::Image(image) => Alternate::Image(image),
+            radk::Alternate::Glyph(glyph) => Alternate::Glyph(single_char(glyph)?),
+            radk::Alternate::None => Alternate::None,
+        };
+        writer.membership(
+            single_char(&radical.glyph)?,
+            radical.strokes,
+            alternate,
+            &chars(&membership.kanji)?,
+        )?;
+    }
+    Ok(writer.finish())
+}
+
+fn chars(glyphs: &[String]) -> Result<Vec<char>, FormatError> {
+    glyphs.iter().map(|glyph| single_char(glyph)).collect()
+}
+
+/// The binary format stores each glyph as one code point
+fn single_char(glyph: &str) -> Result<char, FormatError> {
+    let mut chars = glyph.chars();
+    match (chars.next(), chars.next()) {
+        (Some(c), None) => Ok(c),
+        _ => Err(FormatError::NotACodePoint(glyph.to_string())),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use kradical_binary::Data;
+    use kradical_parsing::test_support::{decomposition, membership};
+
+    #[test]
+    fn round_trip() {
+        let decompositions = vec![decomposition("亜", &["｜", "一", "口"])];
+        let mut memberships = vec![membership("⻌", 3, &["込"])];
+        memberships[0].radical.alternate = radk::Alternate::Glyph("辶".to_string());
+        let bytes = from_dataset(&decompositions, &memberships).unwrap();
+        let data = Data::new(&bytes).unwrap();
+        let decomposition = data.decompositions().next().unwrap();
+        assert_eq!(decomposition.kanji, '亜');
+        assert_eq!(decomposition.radicals.collect::<String>(), "｜一口");
+        let membership = data.memberships().next().unwrap();
+        assert_eq!(membership.radical, '⻌');
+        assert_eq!(membership.alternate, Alternate::Glyph('辶'));
+    }
+
+    #[test]
+    fn rejects_sequences() {
+        assert!(single_char("か゚").is_err());
+    }
+}
@@ -0,0 +1,137 @@
+use std::{
+    collections::BTreeMap,
+    fmt::{self, Display, Formatter},
+};
+
+use crate::report::heading;
+use kradical_parsing::{
+    bmp::{fallback, is_bmp},
+    krad::Decomposition,
+    radk::{Alternate, Membership},
+};
+use serde::Serialize;
+
+/// The characters outside the Basic Multilingual Plane in converted data
+#[derive(Debug, Default, Clone, PartialEq, Eq, Serialize)]
+pub struct BmpReport {
+    pub characters: Vec<OutsideBmp>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct OutsideBmp {
+    pub character: String,
+
+    /// The code point, as in `U+201A2`
+    pub codepoint: String,
+
+    /// Appearances as a radical or alternate glyph
+    pub radical: usize,
+
+    /// Appearances as a kanji
+    pub kanji: usize,
+
+    /// The character that replaces it, if any
+    pub fallback: Option<String>,
+}
+
+/// Appearances as a radical and as a kanji
+type Counts = BTreeMap<char, (usize, usize)>;
+
+impl BmpReport {
+    /// Finds the characters outside the BMP in whichever file families
+    /// were parsed
+    pub fn new(
+        decompositions: Option<&[Decomposition]>,
+        memberships: Option<&[Membership]>,
+    ) -> Self {
+        let mut counts = Counts::new();
+        for decomposition in decompositions.unwrap_or_default() {
+            count(&mut counts, &decomposition.kanji, false);
+            for radical in decomposition.radicals.iter() {
+                count(&mut counts, radical, true);
+            }
+        }
+        for membership in memberships.unwrap_or_default() {
+            count(&mut counts, &membership.radical.glyph, true);
+            if let Alternate::Glyph(glyph) = &membership.radical.alternate {
+                count(&mut counts, glyph, true);
+            }
+            for kanji in membership.kanji.iter() {
+                count(&mut counts, kanji, false);
+            }
+        }
+        let characters = counts
+            .into_iter()
+            .map(|(c, (radical, kanji))| OutsideBmp {
+                character: c.to_string(),
+                codepoint: format!("U+{:X}", c as u32),
+                radical,
+                kanji,
+                fallback: fallback(c).map(String::from),
+            })
+            .collect();
+        Self { characters }
+    }
+}
+
+fn count(counts: &mut Counts, text: &str, radical: bool) {
+    for c in text.chars().filter(|&c| !is_bmp(c)) {
+        let (radicals, kanji) = counts.entry(c).or_default();
+        match radical {
+            true => *radicals += 1,
+            false => *kanji += 1,
+        }
+    }
+}
+
+impl Display for BmpReport {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        heading(f, "Outside the BMP", self.characters.len())?;
+        for outside in self.characters.iter() {
+            write!(
+                f,
+                "  {} {} : {} as a radical, {} as a kanji, ",
+                outside.character, outside.codepoint, outside.radical, outside.kanji
+            )?;
+            match &outside.fallback {
+                Some(fallback) => writeln!(f, "falls back to {}", fallback)?,
+                None => writeln!(f, "no fallback")?,
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use kradical_parsing::test_support::decomposition;
+
+    #[test]
+    fn counts_characters() {
+        let decompositions = [
+            decomposition("乞", &["\u{20089}", "乙"]),
+            decomposition("\u{20000}", &["\u{20089}"]),
+        ];
+        let report = BmpReport::new(Some(&decompositions), None);
+        assert_eq!(
+            report.characters,
+            vec![
+                OutsideBmp {
+                    character: "\u{20000}".to_string(),
+                    codepoint: "U+20000".to_string(),
+                    radical: 0,
+                    kanji: 1,
+                    fallback: None,
+                },
+                OutsideBmp {
+                    character: "\u{20089}".to_string(),
+                    codepoint: "U+20089".to_string(),
+                    radical: 2,
+                    kanji: 0,
+                    fallback: Some("乞".to_string()),
+                },
+            ]
+        );
+    }
+}
@@ -0,0 +1,325 @@
+use crate::codegen::write_lines;
+use kradical_parsing::radk::{Alternate, Membership};
+use std::{
+    collections::BTreeMap,
+    io::{self, Write},
+};
+
+/// The number of cells in each row of the SVG chart
+const COLUMNS: usize = 18;
+
+/// The width and height of a cell in the SVG chart
+const CELL: usize = 40;
+
+/// The number of kanji in each line of the SVG kanji lists
+const KANJI_PER_LINE: usize = 24;
+
+/// The height of a line in the SVG kanji lists
+const LINE: usize = 28;
+
+/// Shows the kanji list of the radical picked in the SVG chart
+const SVG_STYLE: &str =
+    ".kanji { display: none; } .kanji:target { display: inline; } a:hover rect { fill: #eee; }";
+
+const STYLE: &str = "
+body { font-family: sans-serif; margin: 2em; }
+.picker { display: flex; flex-wrap: wrap; gap: 2px; margin-bottom: 2em; }
+.picker a, .picker span { width: 2em; height: 2em; display: flex; align-items: center; justify-content: center; font-size: 1.4em; text-decoration: none; color: inherit; border: 1px solid #ccc; }
+.picker span { background: #333; color: #fff; font-size: 1em; }
+.picker img, h2 img { height: 1em; }
+section { break-inside: avoid; border-top: 1px solid #ccc; }
+h2 small { font-weight: normal; font-size: 0.6em; color: #666; }
+.kanji { font-size: 1.2em; line-height: 1.6; }
+";
+
+/// A self-contained HTML page listing the radicals by stroke count,
+/// each linked to the kanji containing it
+pub fn write_html(
+    memberships: &[Membership],
+    image_url: Option<&str>,
+    out: &mut dyn Write,
+) -> io::Result<()> {
+    write_lines(
+        out,
+        &[
+            "<!DOCTYPE html>",
+            "<html lang=\"ja\">",
+            "<head>",
+            "<meta charset=\"utf-8\">",
+            "<title>Radicals by stroke count</title>",
+        ],
+    )?;
+    writeln!(out, "<style>{}</style>", STYLE)?;
+    write_lines(
+        out,
+        &[
+            "</head>",
+            "<body>",
+            "<h1>Radicals by stroke count</h1>",
+            "<nav class=\"picker\">",
+        ],
+    )?;
+    let groups = by_strokes(memberships);
+    for (strokes, group) in groups.iter() {
+        writeln!(out, "<span>{}</span>", strokes)?;
+        for &(i, membership) in group {
+            writeln!(
+                out,
+                "<a href=\"#radical-{}\" title=\"{}\">{}</a>",
+                i,
+                escape(&membership.radical.glyph),
+                html_glyph(membership, image_url)
+            )?;
+        }
+    }
+    writeln!(out, "</nav>")?;
+    for &(i, membership) in groups.values().flatten() {
+        writeln!(out, "<section id=\"radical-{}\">", i)?;
+        writeln!(
+            out,
+            "<h2>{} <small>{} strokes, {} kanji</small></h2>",
+            html_glyph(membership, image_url),
+            membership.radical.strokes,
+            membership.kanji.len()
+        )?;
+        writeln!(
+            out,
+            "<p class=\"kanji\">{}</p>",
+            escape(&membership.kanji.join(" "))
+        )?;
+        writeln!(out, "</section>")?;
+    }
+    write_lines(out, &["</body>", "</html>"])
+}
+
+/// An SVG grid of the radicals by stroke count. Picking a radical shows
+/// the kanji containing it below the grid.
+pub fn write_svg(
+    memberships: &[Membership],
+    image_url: Option<&str>,
+    out: &mut dyn Write,
+) -> io::Result<()> {
+    let groups = by_strokes(memberships);
+    let mut cells = vec![];
+    for (&strokes, group) in groups.iter() {
+        cells.push(Cell::Strokes(strokes));
+        cells.extend(
+            group
+                .iter()
+                .map(|&(i, membership)| Cell::Radical(i, membership)),
+        );
+    }
+    let grid_height = cells.len().div_ceil(COLUMNS) * CELL;
+    let list_lines = memberships
+        .iter()
+        .map(|membership| 1 + membership.kanji.len().div_ceil(KANJI_PER_LINE))
+        .max()
+        .unwrap_or_default();
+    let (width, height) = (COLUMNS * CELL, grid_height + list_lines * LINE + LINE / 2);
+    writeln!(
+        out,
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{0}\" height=\"{1}\" viewBox=\"0 0 {0} {1}\" font-family=\"sans-serif\" text-anchor=\"middle\" dominant-baseline=\"central\">",
+        width, height
+    )?;
+    writeln!(out, "<title>Radicals by stroke count</title>")?;
+    writeln!(out, "<style>{}</style>", SVG_STYLE)?;
+    for (i, cell) in cells.iter().enumerate() {
+        let x = i % COLUMNS * CELL;
+        let y = i / COLUMNS * CELL;
+        let (cx, cy) = (x + CELL / 2, y + CELL / 2);
+        match cell {
+            Cell::Strokes(strokes) => {
+                writeln!(
+                    out,
+                    "<rect x=\"{}\" y=\"{}\" width=\"{}\" height=\"{}\" fill=\"#333\"/>",
+                    x, y, CELL, CELL
+                )?;
+                writeln!(
+                    out,
+                    "<text x=\"{}\" y=\"{}\" font-size=\"16\" fill=\"#fff\">{}</text>",
+                    cx, cy, strokes
+                )?;
+            }
+            Cell::Radical(index, membership) => {
+                writeln!(out, "<a href=\"#radical-{}\">", index)?;
+                writeln!(
+                    out,
+                    "<title>{} ({} kanji): {}</title>",
+                    escape(&membership.radical.glyph),
+                    membership.kanji.len(),
+                    escape(&membership.kanji.join(" "))
+                )?;
+                writeln!(
+                    out,
+                    "<rect x=\"{}\" y=\"{}\" width=\"{}\" height=\"{}\" fill=\"#fff\" stroke=\"#ccc\"/>",
+                    x, y, CELL, CELL
+                )?;
+                match image(membership, image_url) {
+                    Some(url) => writeln!(
+                        out,
+                        "<image x=\"{}\" y=\"{}\" width=\"{}\" height=\"{}\" href=\"{}\"/>",
+                        x + 6,
+                        y + 6,
+                        CELL - 12,
+                        CELL - 12,
+                        escape(&url)
+                    )?,
+                    None => writeln!(
+                        out,
+                        "<text x=\"{}\" y=\"{}\" font-size=\"24\">{}</text>",
+                        cx,
+                        cy,
+                        escape(display_glyph(membership))
+                    )?,
+                }
+                writeln!(out, "</a>")?;
+            }
+        }
+    }
+    for &(i, membership) in groups.values().flatten() {
+        writeln!(
+            out,
+            "<g id=\"radical-{}\" class=\"kanji\" text-anchor=\"start\" font-size=\"20\">",
+            i
+        )?;
+        let top = grid_height + LINE;
+        writeln!(
+            out,
+            "<text x=\"8\" y=\"{}\">{} : {} strokes, {} kanji</text>",
+            top,
+            escape(display_glyph(membership)),
+            membership.radical.strokes,
+            membership.kanji.len()
+        )?;
+        for (line, kanji) in membership.kanji.chunks(KANJI_PER_LINE).enumerate() {
+            writeln!(
+                out,
+                "<text x=\"8\" y=\"{}\">{}</text>",
+                top + (line + 1) * LINE,
+                escape(&kanji.join(" "))
+            )?;
+        }
+        writeln!(out, "</g>")?;
+    }
+    writeln!(out, "</svg>")
+}
+
+/// The memberships by stroke count, each with its index, keeping
+/// the order they arrive in within each stroke count
+fn by_strokes(memberships: &[Membership]) -> BTreeMap<u8, Vec<(usize, &Membership)>> {
+    let mut groups: BTreeMap<_, Vec<_>> = BTreeMap::new();
+    for (i, membership) in memberships.iter().enumerate() {
+        groups
+            .entry(membership.radical.strokes)
+            .or_default()
+            .push((i, membership));
+    }
+    groups
+}
+
+enum Cell<'a> {
+    Strokes(u8),
+
+    /// A radical with its index among the memberships
+    Radical(usize, &'a Membership),
+}
+
+fn html_glyph(membership: &Membership, image_url: Option<&str>) -> String {
+    match image(membership, image_url) {
+        Some(url) => format!(
+            "<img src=\"{}\" alt=\"{}\">",
+            escape(&url),
+            escape(&membership.radical.glyph)
+        ),
+        None => escape(display_glyph(membership)),
+    }
+}
+
+/// The glyph to show, preferring an alternate glyph
+/// where the primary one renders poorly
+fn display_glyph(membership: &Membership) -> &str {
+    match &membership.radical.alternate {
+        Alternate::Glyph(glyph) => glyph,
+        Alternate::Image(_) | Alternate::None => &membership.radical.glyph,
+    }
+}
+
+/// The URL of an alternate image, where `{}` in the template is the image name
+fn image(membership: &Membership, image_url: Option<&str>) -> Option<String> {
+    match (&membership.radical.alternate, image_url) {
+        (Alternate::Image(name), Some(template)) => Some(template.replace("{}", name)),
+        _ => None,
+    }
+}
+
+fn escape(s: &str) -> String {
+    s.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use kradical_parsing::test_support::membership;
+
+    fn memberships() -> Vec<Membership> {
+        let mut memberships = vec![
+            membership("一", 1, &["丁"]),
+            membership("⺅", 2, &["化", "仕"]),
+            membership("⻌", 3, &["込"]),
+        ];
+        memberships[1].radical.alternate = Alternate::Image("js01".to_string());
+        memberships[2].radical.alternate = Alternate::Glyph("辶".to_string());
+        memberships
+    }
+
+    #[test]
+    fn html_groups_by_strokes() {
+        let mut html = vec![];
+        write_html(&memberships(), Some("img/{}.png"), &mut html).unwrap();
+        let html = String::from_utf8(html).unwrap();
+        assert!(html.contains("<span>1</span>\n<a href=\"#radical-0\" title=\"一\">一</a>"));
+        assert!(html.contains("<img src=\"img/js01.png\" alt=\"⺅\">"));
+        assert!(html.contains("title=\"⻌\">辶</a>"));
+        assert!(html.contains("<p class=\"kanji\">化 仕</p>"));
+    }
+
+    #[test]
+    fn svg_falls_back_to_glyphs() {
+        let mut svg = vec![];
+        write_svg(&memberships(), None, &mut svg).unwrap();
+        let svg = String::from_utf8(svg).unwrap();
+        assert!(svg.contains(">⺅</text>"));
+        assert!(svg.contains("<title>⺅ (2 kanji): 化 仕</title>"));
+        assert_eq!(svg.matches("fill=\"#333\"").count(), 3);
+    }
+
+    #[test]
+    fn groups_unsorted_radicals() {
+        let mut memberships = memberships();
+        memberships.reverse();
+        let mut html = vec![];
+        write_html(&memberships, None, &mut html).unwrap();
+        let html = String::from_utf8(html).unwrap();
+        assert_eq!(html.matches("<span>").count(), 3);
+        assert!(html.find("<span>1</span>") < html.find("<span>3</span>"));
+        assert!(html.find("id=\"radical-2\"") < html.find("id=\"radical-0\""));
+    }
+
+    #[test]
+    fn svg_links_radicals_to_kanji() {
+        let mut svg = vec![];
+        write_svg(&memberships(), None, &mut svg).unwrap();
+        let svg = String::from_utf8(svg).unwrap();
+        assert!(svg.contains("<a href=\"#radical-1\">"));
+        assert!(svg.contains("<g id=\"radical-1\" class=\"kanji\""));
+        assert!(svg.contains(">化 仕</text>"));
+    }
+
+    #[test]
+    fn escapes_markup() {
+        assert_eq!(escape("<a href=\"&\">"), "&lt;a href=&quot;&amp;&quot;&gt;");
+    }
+}
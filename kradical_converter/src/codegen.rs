@@ -0,0 +1,62 @@
+use std::io::{self, Write};
+
+/// A double-quoted string literal for TypeScript or Python
+pub fn quoted(s: &str) -> String {
+    serde_json::to_string(s).expect("Strings are always serializable")
+}
+
+/// A string literal for C with non-ASCII bytes written as octal escapes
+/// so that the header does not depend on the compiler's source encoding
+pub fn c_string(s: &str) -> String {
+    let mut literal = String::from("\"");
+    for byte in s.bytes() {
+        match byte {
+            b'"' | b'\\' => {
+                literal.push('\\');
+                literal.push(byte as char);
+            }
+            0x20..=0x7E => literal.push(byte as char),
+            _ => literal.push_str(&format!("\\{:03o}", byte)),
+        }
+    }
+    literal.push('"');
+    literal
+}
+
+/// A Python tuple, which needs a trailing comma when it has one item
+pub fn python_tuple(items: &[String]) -> String {
+    match items {
+        [item] => format!("({},)", item),
+        _ => format!("({})", items.join(", ")),
+    }
+}
+
+/// Writes each line followed by a newline
+pub fn write_lines(out: &mut dyn Write, lines: &[&str]) -> io::Result<()> {
+    for line in lines {
+        writeln!(out, "{}", line)?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn quotes_strings() {
+        assert_eq!(quoted("一\""), "\"一\\\"\"");
+    }
+
+    #[test]
+    fn escapes_c_strings() {
+        assert_eq!(c_string("一"), "\"\\344\\270\\200\"");
+        assert_eq!(c_string("js02"), "\"js02\"");
+    }
+
+    #[test]
+    fn single_item_tuple() {
+        assert_eq!(python_tuple(&["1".to_string()]), "(1,)");
+        assert_eq!(python_tuple(&["1".to_string(), "2".to_string()]), "(1, 2)");
+    }
+}
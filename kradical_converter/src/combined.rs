@@ -0,0 +1,351 @@
+use crate::{
+    error::ConvertError,
+    json::{self, JsonStyle},
+    kanjidic::{self, Frequencies, Meanings},
+    krad,
+    opts::{Layout, Merge, Order},
+    order,
+    radk::{self, AlternateRecord},
+    table::{self, Delimiter},
+};
+use kradical_parsing::{
+    krad::Decomposition,
+    radk::{Membership, Radical},
+};
+use serde::Serialize;
+use std::io::{self, Write};
+
+/// The contents of both file families
+pub struct Dataset {
+    pub decompositions: Vec<Decomposition>,
+    pub memberships: Vec<Membership>,
+
+    /// English meanings from KANJIDIC2, if given
+    pub meanings: Meanings,
+
+    /// Frequency ranks from KANJIDIC2, if given
+    pub frequencies: Option<Frequencies>,
+}
+
+/// A kanji whose radicals are joined with their details from the `radkfile`
+struct Kanji<'a> {
+    kanji: &'a str,
+    radicals: Vec<Component<'a>>,
+}
+
+/// A radical of a kanji, which may be missing from the `radkfile`
+struct Component<'a> {
+    glyph: &'a str,
+    radical: Option<&'a Radical>,
+}
+
+impl Dataset {
+    /// Sorts both file families as in the krad and radk commands
+    pub fn sort(&mut self, order: Option<Order>) -> Result<(), ConvertError> {
+        order::sort_decompositions(
+            &mut self.decompositions,
+            order.unwrap_or(Order::File),
+            Some(&self.memberships),
+            self.frequencies.as_ref(),
+        )?;
+        order::sort_memberships(
+            &mut self.memberships,
+            order.unwrap_or(Order::Strokes),
+            self.frequencies.as_ref(),
+        )
+    }
+
+    fn kanji(&self) -> Vec<Kanji<'_>> {
+        let radicals = radk::details(&self.memberships);
+        self.decompositions
+            .iter()
+            .map(|decomposition| Kanji {
+                kanji: &decomposition.kanji,
+                radicals: decomposition
+                    .radicals
+                    .iter()
+                    .map(|glyph| Component {
+                        glyph,
+                        radical: radicals.get(glyph.as_str()).copied(),
+                    })
+                    .collect(),
+            })
+            .collect()
+    }
+}
+
+/// Parses both file families, merging repeated kanji and radicals
+pub fn parse(
+    krad_inputs: &[String],
+    radk_inputs: &[String],
+    kanjidic: Option<&str>,
+    merge: Merge,
+) -> Result<Dataset, ConvertError> {
+    if radk_inputs.is_empty() {
+        return Err(ConvertError::MissingRadkInputs);
+    }
+    let (meanings, frequencies) = match kanjidic {
+        Some(path) => {
+            let kanjidic = kanjidic::parse(path)?;
+            (kanjidic.meanings, Some(kanjidic.frequencies))
+        }
+        None => (Meanings::new(), None),
+    };
+    Ok(Dataset {
+        decompositions: krad::parse_merged(krad_inputs, merge)?,
+        memberships: radk::parse_merged(radk_inputs, merge)?,
+        meanings,
+        frequencies,
+    })
+}
+
+/// The JSON representation of the dataset
+#[derive(Serialize)]
+struct Record<'a> {
+    kanji: Vec<KanjiRecord<'a>>,
+    radicals: Vec<radk::Record<'a>>,
+}
+
+/// The JSON representation of a kanji
+#[derive(Serialize)]
+struct KanjiRecord<'a> {
+    kanji: &'a str,
+    radicals: Vec<ComponentRecord<'a>>,
+}
+
+/// The JSON representation of a radical within a kanji
+#[derive(Serialize)]
+struct ComponentRecord<'a> {
+    radical: &'a str,
+    stroke: Option<u8>,
+    alternate: Option<AlternateRecord<'a>>,
+}
+
+/// A line of NDJSON output
+#[derive(Serialize)]
+#[serde(tag = "type", rename_all = "lowercase")]
+enum Line<'a> {
+    Kanji(KanjiRecord<'a>),
+    Radical(radk::Record<'a>),
+}
+
+fn to_kanji_records<'a>(kanji: &[Kanji<'a>]) -> Vec<KanjiRecord<'a>> {
+    kanji
+        .iter()
+        .map(|kanji| KanjiRecord {
+            kanji: kanji.kanji,
+            radicals: kanji
+                .radicals
+                .iter()
+                .map(|component| ComponentRecord {
+                    radical: component.glyph,
+                    stroke: component.radical.map(|radical| radical.strokes),
+                    alternate: component
+                        .radical
+                        .and_then(|radical| AlternateRecord::new(&radical.alternate)),
+                })
+                .collect(),
+        })
+        .collect()
+}
+
+fn to_record(dataset: &Dataset) -> Record<'_> {
+    Record {
+        kanji: to_kanji_records(&dataset.kanji()),
+        radicals: radk::to_records(&dataset.memberships),
+    }
+}
+
+fn to_lines(dataset: &Dataset) -> Vec<Line<'_>> {
+    let Record { kanji, radicals } = to_record(dataset);
+    kanji
+        .into_iter()
+        .map(Line::Kanji)
+        .chain(radicals.into_iter().map(Line::Radical))
+        .collect()
+}
+
+pub fn write_json(
+    dataset: &Dataset,
+    style: JsonStyle,
+    out: &mut dyn Write,
+) -> serde_json::Result<()> {
+    json::write_json(&to_record(dataset), style, out)
+}
+
+pub fn write_ndjson(dataset: &Dataset, out: &mut dyn Write) -> serde_json::Result<()> {
+    json::write_ndjson(to_lines(dataset), out)
+}
+
+pub fn write_table(
+    dataset: &Dataset,
+    layout: Layout,
+    delimiter: Delimiter,
+    out: &mut dyn Write,
+) -> Result<(), csv::Error> {
+    let kanji = dataset.kanji();
+    match layout {
+        // Alternates line up with the radicals, so a radical without one
+        // is written as `-` and a radical missing from the radkfiles as `?`
+        Layout::Wide => table::write_table(
+            &["kanji", "radicals", "strokes", "alternates"],
+            kanji.iter().map(|kanji| {
+                let glyphs: Vec<_> = kanji.radicals.iter().map(|c| c.glyph).collect();
+                let strokes: Vec<_> = kanji
+                    .radicals
+                    .iter()
+                    .map(|component| match component.radical {
+                        Some(radical) => radical.strokes.to_string(),
+                        None => "?".to_string(),
+                    })
+                    .collect();
+                let alternates: Vec<_> = kanji
+                    .radicals
+                    .iter()
+                    .map(|component| match component.radical {
+                        Some(radical) => radk::alternate_text(&radical.alternate).unwrap_or("-"),
+                        None => "?",
+                    })
+                    .collect();
+                vec![
+                    kanji.kanji.to_string(),
+                    glyphs.join(" "),
+                    strokes.join(" "),
+                    alternates.join(" "),
+                ]
+            }),
+            delimiter,
+            out,
+        ),
+        Layout::Long => table::write_table(
+            &["kanji", "radical", "strokes", "alternate"],
+            kanji.iter().flat_map(|kanji| {
+                kanji.radicals.iter().map(move |component| {
+                    let strokes = component
+                        .radical
+                        .map(|radical| radical.strokes.to_string())
+                        .unwrap_or_default();
+                    let alternate = component
+                        .radical
+                        .and_then(|radical| radk::alternate_text(&radical.alternate))
+                        .unwrap_or_default();
+                    vec![
+                        kanji.kanji.to_string(),
+                        component.glyph.to_string(),
+                        strokes,
+                        alternate.to_string(),
+                    ]
+                })
+            }),
+            delimiter,
+            out,
+        ),
+    }
+}
+
+/// Notes for import into Anki, with a header naming the note fields
+pub fn write_anki(dataset: &Dataset, out: &mut dyn Write) -> io::Result<()> {
+    writeln!(out, "#separator:tab")?;
+    writeln!(out, "#html:false")?;
+    writeln!(out, "#columns:Kanji\tRadicals\tStrokes\tMeanings")?;
+    for kanji in dataset.kanji() {
+        let glyphs: Vec<_> = kanji.radicals.iter().map(|c| c.glyph).collect();
+        let strokes: Vec<_> = kanji
+            .radicals
+            .iter()
+            .map(|component| match component.radical {
+                Some(radical) => radical.strokes.to_string(),
+                None => "?".to_string(),
+            })
+            .collect();
+        let meanings = dataset
+            .meanings
+            .get(kanji.kanji)
+            .map(|meanings| meanings.join(", "))
+            .unwrap_or_default();
+        let fields = [
+            kanji.kanji.to_string(),
+            glyphs.join(" "),
+            strokes.join(" "),
+            meanings,
+        ];
+        let fields: Vec<_> = fields.iter().map(|field| anki_field(field)).collect();
+        writeln!(out, "{}", fields.join("\t"))?;
+    }
+    Ok(())
+}
+
+/// Anki reads each line as a note, so fields cannot hold tabs or newlines
+fn anki_field(field: &str) -> String {
+    field.replace(['\t', '\n', '\r'], " ")
+}
+
+pub fn write_unicode(dataset: &Dataset, out: &mut dyn Write) -> io::Result<()> {
+    krad::write_unicode(&dataset.decompositions, out)?;
+    write!(out, "\n\n")?;
+    radk::write_unicode(&dataset.memberships, out)
+}
+
+pub fn write_rust(dataset: &Dataset, out: &mut dyn Write) -> io::Result<()> {
+    krad::write_rust(&dataset.decompositions, out)?;
+    write!(out, "\n\n")?;
+    radk::write_rust(&dataset.memberships, out)
+}
+
+/// Generated code for both file families, one after the other
+pub fn write_code(
+    dataset: &Dataset,
+    krad: fn(&[Decomposition], &mut dyn Write) -> io::Result<()>,
+    radk: fn(&[Membership], &mut dyn Write) -> io::Result<()>,
+    out: &mut dyn Write,
+) -> io::Result<()> {
+    krad(&dataset.decompositions, out)?;
+    writeln!(out)?;
+    radk(&dataset.memberships, out)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use kradical_parsing::radk::Alternate;
+    use kradical_parsing::test_support::{decomposition, membership};
+
+    #[test]
+    fn anki_notes() {
+        let dataset = Dataset {
+            decompositions: vec![decomposition("悒", &["口", "邑"])],
+            memberships: vec![membership("口", 3, &["悒"])],
+            meanings: vec![(
+                "悒".to_string(),
+                vec!["melancholy".to_string(), "worry\tabout".to_string()],
+            )]
+            .into_iter()
+            .collect(),
+            frequencies: None,
+        };
+        let mut notes = vec![];
+        write_anki(&dataset, &mut notes).unwrap();
+        assert_eq!(
+            String::from_utf8(notes).unwrap().lines().last(),
+            Some("悒\t口 邑\t3 ?\tmelancholy, worry about")
+        );
+    }
+
+    #[test]
+    fn wide_table_lists_alternates() {
+        let mut memberships = vec![membership("⻌", 3, &["込"]), membership("入", 2, &["込"])];
+        memberships[0].radical.alternate = Alternate::Glyph("辶".to_string());
+        let dataset = Dataset {
+            decompositions: vec![decomposition("込", &["⻌", "入", "乂"])],
+            memberships,
+            meanings: Default::default(),
+            frequencies: None,
+        };
+        let mut table = vec![];
+        write_table(&dataset, Layout::Wide, Delimiter::Comma, &mut table).unwrap();
+        assert_eq!(
+            String::from_utf8(table).unwrap(),
+            "kanji,radicals,strokes,alternates\r\n込,⻌ 入 乂,3 2 ?,辶 - ?\r\n"
+        );
+    }
+}
@@ -0,0 +1,260 @@
+use crate::{
+    bmp::BmpReport,
+    combined,
+    error::{ConvertError, FormatError},
+    files,
+    filter::Filter,
+    format::{self, Formatter},
+    glyphs,
+    kanjidic::{self, Frequencies},
+    krad,
+    opts::{Command, ConvertOpts, InputFormat, Order, OutputFormat},
+    order, overlay, radk, report,
+};
+use kradical_parsing::{bmp, invert, krad::Decomposition, radk::Membership};
+
+/// A converted file ready to be written
+pub struct Generated<'a> {
+    pub format: OutputFormat,
+    pub path: &'a str,
+    pub bytes: Vec<u8>,
+}
+
+/// Runs a conversion command without writing its outputs
+pub fn run(command: &Command) -> Result<Vec<Generated<'_>>, ConvertError> {
+    match command {
+        Command::Radk(opts) => convert_radk(opts),
+        Command::Krad(opts) => convert_krad(opts),
+        Command::Combined(opts) => convert_combined(opts),
+        Command::KradToRadk(opts) => krad_to_radk(opts),
+        Command::RadkToKrad(opts) => radk_to_krad(opts),
+        _ => Err(ConvertError::NotAConversion),
+    }
+}
+
+pub fn write(generated: &[Generated<'_>]) -> Result<(), ConvertError> {
+    for output in generated {
+        files::write(output.path, &output.bytes)?;
+    }
+    Ok(())
+}
+
+/// Formats the converted data once for each output
+fn generate<'a>(
+    opts: &ConvertOpts,
+    outputs: Vec<(OutputFormat, &'a str)>,
+    write: impl Fn(&dyn Formatter, &mut Vec<u8>) -> Result<(), FormatError>,
+) -> Result<Vec<Generated<'a>>, ConvertError> {
+    outputs
+        .into_iter()
+        .map(|(output_format, path)| {
+            let mut bytes = vec![];
+            write(
+                &*format::formatter(output_format, opts.format_options()),
+                &mut bytes,
+            )
+            .map_err(|source| ConvertError::Output {
+                path: path.to_string(),
+                source,
+            })?;
+            Ok(Generated {
+                format: output_format,
+                path,
+                bytes,
+            })
+        })
+        .collect()
+}
+
+fn convert_radk(opts: &ConvertOpts) -> Result<Vec<Generated<'_>>, ConvertError> {
+    let outputs = opts.outputs()?;
+    let mut memberships = radk::parse_merged(&opts.inputs, opts.merge)?;
+    apply_overlay(opts, None, Some(&mut memberships))?;
+    Filter::new(opts, InputFormat::Radk)?.memberships(&mut memberships);
+    if let Some(repertoire) = opts.radical_glyphs {
+        glyphs::memberships(&mut memberships, repertoire.into());
+    }
+    apply_bmp(opts, None, Some(&mut memberships))?;
+    order::sort_memberships(
+        &mut memberships,
+        opts.order.unwrap_or(Order::Strokes),
+        frequencies(opts)?.as_ref(),
+    )?;
+    generate(opts, outputs, |formatter, out| {
+        formatter.memberships(&memberships, out)
+    })
+}
+
+fn convert_krad(opts: &ConvertOpts) -> Result<Vec<Generated<'_>>, ConvertError> {
+    let outputs = opts.outputs()?;
+    let mut decompositions = krad::parse_merged(&opts.inputs, opts.merge)?;
+    let mut radicals = match opts.radk_inputs.is_empty() {
+        true => None,
+        false => Some(radk::parse_merged(&opts.radk_inputs, opts.merge)?),
+    };
+    apply_overlay(opts, Some(&mut decompositions), radicals.as_mut())?;
+    Filter::new(opts, InputFormat::Krad)?.decompositions(&mut decompositions);
+    if let Some(repertoire) = opts.radical_glyphs {
+        glyphs::decompositions(&mut decompositions, repertoire.into());
+        if let Some(radicals) = radicals.as_mut() {
+            glyphs::memberships(radicals, repertoire.into());
+        }
+    }
+    apply_bmp(opts, Some(&mut decompositions), None)?;
+    if let (true, Some(radicals)) = (opts.bmp, radicals.as_mut()) {
+        bmp::memberships(radicals)?;
+    }
+    order::sort_decompositions(
+        &mut decompositions,
+        opts.order.unwrap_or(Order::File),
+        radicals.as_deref(),
+        frequencies(opts)?.as_ref(),
+    )?;
+    // Charts list the radicals as the radk command would
+    if let Some(radicals) = radicals.as_mut() {
+        order::sort_memberships(radicals, Order::Strokes, None)?;
+    }
+    generate(opts, outputs, |formatter, out| {
+        formatter.decompositions_with_radk(&decompositions, radicals.as_deref(), out)
+    })
+}
+
+fn convert_combined(opts: &ConvertOpts) -> Result<Vec<Generated<'_>>, ConvertError> {
+    let outputs = opts.outputs()?;
+    let mut dataset = combined::parse(
+        &opts.inputs,
+        &opts.radk_inputs,
+        opts.kanjidic.as_deref(),
+        opts.merge,
+    )?;
+    apply_overlay(
+        opts,
+        Some(&mut dataset.decompositions),
+        Some(&mut dataset.memberships),
+    )?;
+    let filter = Filter::new(opts, InputFormat::Krad)?;
+    filter.decompositions(&mut dataset.decompositions);
+    filter.memberships(&mut dataset.memberships);
+    if let Some(repertoire) = opts.radical_glyphs {
+        glyphs::decompositions(&mut dataset.decompositions, repertoire.into());
+        glyphs::memberships(&mut dataset.memberships, repertoire.into());
+    }
+    apply_bmp(
+        opts,
+        Some(&mut dataset.decompositions),
+        Some(&mut dataset.memberships),
+    )?;
+    dataset.sort(opts.order)?;
+    generate(opts, outputs, |formatter, out| {
+        formatter.dataset(&dataset, out)
+    })
+}
+
+fn krad_to_radk(opts: &ConvertOpts) -> Result<Vec<Generated<'_>>, ConvertError> {
+    if opts.radk_inputs.is_empty() {
+        return Err(ConvertError::MissingRadkInputs);
+    }
+    let outputs = opts.outputs()?;
+    let filter = Filter::new(opts, InputFormat::Krad)?;
+    let mut decompositions = krad::parse_merged(&opts.inputs, opts.merge)?;
+    let mut radicals = radk::parse(&opts.radk_inputs)?;
+    apply_overlay(opts, Some(&mut decompositions), Some(&mut radicals))?;
+    filter.decompositions(&mut decompositions);
+    let radicals = invert::radicals(&radicals);
+    let mut memberships = invert::memberships(&decompositions, &radicals);
+    filter.memberships(&mut memberships);
+    if let Some(repertoire) = opts.radical_glyphs {
+        glyphs::memberships(&mut memberships, repertoire.into());
+    }
+    apply_bmp(opts, None, Some(&mut memberships))?;
+    if let Some(order) = opts.order {
+        order::sort_memberships(&mut memberships, order, frequencies(opts)?.as_ref())?;
+    }
+    generate(opts, outputs, |formatter, out| {
+        formatter.memberships(&memberships, out)
+    })
+}
+
+fn radk_to_krad(opts: &ConvertOpts) -> Result<Vec<Generated<'_>>, ConvertError> {
+    let outputs = opts.outputs()?;
+    let mut memberships = radk::parse_merged(&opts.inputs, opts.merge)?;
+    apply_overlay(opts, None, Some(&mut memberships))?;
+    Filter::new(opts, InputFormat::Radk)?.memberships(&mut memberships);
+    if let Some(repertoire) = opts.radical_glyphs {
+        glyphs::memberships(&mut memberships, repertoire.into());
+    }
+    apply_bmp(opts, None, Some(&mut memberships))?;
+    let mut decompositions = invert::decompositions(&memberships);
+    if let Some(order) = opts.order {
+        order::sort_decompositions(
+            &mut decompositions,
+            order,
+            Some(&memberships),
+            frequencies(opts)?.as_ref(),
+        )?;
+    }
+    // Charts list the radicals as the radk command would
+    order::sort_memberships(&mut memberships, Order::Strokes, None)?;
+    generate(opts, outputs, |formatter, out| {
+        formatter.decompositions_with_radk(&decompositions, Some(&memberships), out)
+    })
+}
+
+/// Applies the corrections overlay, if given, and writes its report
+fn apply_overlay(
+    opts: &ConvertOpts,
+    decompositions: Option<&mut Vec<Decomposition>>,
+    memberships: Option<&mut Vec<Membership>>,
+) -> Result<(), ConvertError> {
+    if let Some(path) = &opts.overlay {
+        let report = overlay::parse(path)?.apply(decompositions, memberships);
+        write_report(
+            &report::format(&report, opts.overlay_report_format)?,
+            opts.overlay_report.as_deref(),
+        )?;
+    }
+    Ok(())
+}
+
+/// Replaces characters outside the BMP when asked, after writing the
+/// report of them so that any without a fallback are listed
+fn apply_bmp(
+    opts: &ConvertOpts,
+    decompositions: Option<&mut Vec<Decomposition>>,
+    memberships: Option<&mut Vec<Membership>>,
+) -> Result<(), ConvertError> {
+    if opts.bmp {
+        let report = BmpReport::new(
+            decompositions.as_deref().map(Vec::as_slice),
+            memberships.as_deref().map(Vec::as_slice),
+        );
+        write_report(
+            &report::format(&report, opts.bmp_report_format)?,
+            opts.bmp_report.as_deref(),
+        )?;
+        if let Some(decompositions) = decompositions {
+            bmp::decompositions(decompositions)?;
+        }
+        if let Some(memberships) = memberships {
+            bmp::memberships(memberships)?;
+        }
+    }
+    Ok(())
+}
+
+/// Writes a report to a file, or to stderr when no path is given
+fn write_report(report: &str, path: Option<&str>) -> Result<(), ConvertError> {
+    match path {
+        Some(path) => files::write(path, report.as_bytes())?,
+        None => eprint!("{}", report),
+    }
+    Ok(())
+}
+
+/// Reads the KANJIDIC2 frequency ranks when ordering by frequency
+fn frequencies(opts: &ConvertOpts) -> Result<Option<Frequencies>, ConvertError> {
+    match (opts.order, &opts.kanjidic) {
+        (Some(Order::Frequency), Some(path)) => Ok(Some(kanjidic::parse(path)?.frequencies)),
+        _ => Ok(None),
+    }
+}
@@ -0,0 +1,249 @@
+use std::{
+    collections::HashSet,
+    fmt::{self, Display, Formatter},
+};
+
+use crate::{glyphs::glyph, report::heading};
+use kradical_parsing::{
+    krad::Decomposition,
+    radk::{Alternate, Membership, Radical},
+};
+use kradical_static::{Repertoire, DECOMPOSITIONS, MEMBERSHIPS};
+use serde::Serialize;
+
+/// The radicals and kanji that a font lacks glyphs for
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Coverage {
+    /// The number of radicals checked
+    pub radicals: usize,
+
+    /// The number of kanji checked
+    pub kanji: usize,
+
+    pub missing_radicals: Vec<MissingRadical>,
+    pub missing_kanji: Vec<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct MissingRadical {
+    pub radical: String,
+
+    /// The code point, as in `U+2E85`
+    pub codepoint: String,
+
+    /// Other ways of writing the radical that the font has glyphs for
+    pub suggestions: Vec<Suggestion>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Suggestion {
+    pub glyph: String,
+    pub source: Source,
+}
+
+/// Where a suggested glyph comes from
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Source {
+    /// The alternate glyph from the radkfile
+    Alternate,
+
+    /// The radical written in a repertoire
+    Original,
+    Unified,
+    Radicals,
+}
+
+impl Coverage {
+    /// Checks each radical and kanji with `has_glyph`, which tells
+    /// whether the font can draw a character
+    pub fn new(
+        decompositions: &[Decomposition],
+        memberships: &[Membership],
+        has_glyph: impl Fn(char) -> bool,
+    ) -> Self {
+        let covered = |glyph: &str| glyph.chars().all(&has_glyph);
+        let missing_radicals = memberships
+            .iter()
+            .filter(|membership| !covered(&membership.radical.glyph))
+            .map(|membership| {
+                let radical = &membership.radical.glyph;
+                let mut candidates = vec![];
+                if let Alternate::Glyph(glyph) = &membership.radical.alternate {
+                    candidates.push((glyph.clone(), Source::Alternate));
+                }
+                for (repertoire, source) in [
+                    (Repertoire::Unified, Source::Unified),
+                    (Repertoire::Radicals, Source::Radicals),
+                    (Repertoire::Original, Source::Original),
+                ] {
+                    candidates.push((glyph(radical, repertoire), source));
+                }
+                let mut seen = HashSet::new();
+                let suggestions = candidates
+                    .into_iter()
+                    .filter(|(glyph, _)| {
+                        glyph != radical && covered(glyph) && seen.insert(glyph.clone())
+                    })
+                    .map(|(glyph, source)| Suggestion { glyph, source })
+                    .collect();
+                MissingRadical {
+                    radical: radical.clone(),
+                    codepoint: radical
+                        .chars()
+                        .map(|c| format!("U+{:X}", c as u32))
+                        .collect::<Vec<_>>()
+                        .join(" "),
+                    suggestions,
+                }
+            })
+            .collect();
+
+        let mut seen = HashSet::new();
+        let kanji: Vec<&str> = decompositions
+            .iter()
+            .map(|decomposition| decomposition.kanji.as_str())
+            .filter(|&kanji| seen.insert(kanji))
+            .collect();
+        let missing_kanji = kanji
+            .iter()
+            .filter(|&&kanji| !covered(kanji))
+            .map(|kanji| kanji.to_string())
+            .collect();
+
+        Self {
+            radicals: memberships.len(),
+            kanji: kanji.len(),
+            missing_radicals,
+            missing_kanji,
+        }
+    }
+}
+
+/// The decompositions shipped in `kradical_static`, checked when no
+/// kradfiles are given
+pub fn static_decompositions() -> Vec<Decomposition> {
+    DECOMPOSITIONS
+        .iter()
+        .map(|decomposition| Decomposition {
+            kanji: decomposition.kanji.to_string(),
+            radicals: decomposition.radicals.iter().map(char::to_string).collect(),
+        })
+        .collect()
+}
+
+/// The memberships shipped in `kradical_static`, checked when no
+/// radkfiles are given
+pub fn static_memberships() -> Vec<Membership> {
+    MEMBERSHIPS
+        .iter()
+        .map(|membership| Membership {
+            radical: Radical {
+                glyph: membership.radical.to_string(),
+                strokes: membership.strokes,
+                alternate: match membership.alternate {
+                    kradical_static::Alternate::Image(image) => Alternate::Image(image.to_string()),
+                    kradical_static::Alternate::Glyph(glyph) => Alternate::Glyph(glyph.to_string()),
+                    kradical_static::Alternate::None => Alternate::None,
+                },
+            },
+            kanji: membership.kanji.iter().map(char::to_string).collect(),
+        })
+        .collect()
+}
+
+impl Display for Coverage {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        writeln!(
+            f,
+            "Covers {} of {} radicals and {} of {} kanji",
+            self.radicals - self.missing_radicals.len(),
+            self.radicals,
+            self.kanji - self.missing_kanji.len(),
+            self.kanji
+        )?;
+        heading(f, "Missing radicals", self.missing_radicals.len())?;
+        for missing in self.missing_radicals.iter() {
+            write!(f, "  {} {}", missing.radical, missing.codepoint)?;
+            let suggestions: Vec<_> = missing
+                .suggestions
+                .iter()
+                .map(|suggestion| format!("{} ({})", suggestion.glyph, suggestion.source))
+                .collect();
+            if !suggestions.is_empty() {
+                write!(f, " : try {}", suggestions.join(", "))?;
+            }
+            writeln!(f)?;
+        }
+        heading(f, "Missing kanji", self.missing_kanji.len())?;
+        if !self.missing_kanji.is_empty() {
+            writeln!(f, "  {}", self.missing_kanji.join(" "))?;
+        }
+        Ok(())
+    }
+}
+
+impl Display for Source {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let source = match self {
+            Self::Alternate => "alternate",
+            Self::Original => "original",
+            Self::Unified => "unified",
+            Self::Radicals => "radicals",
+        };
+        write!(f, "{}", source)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use kradical_parsing::test_support::{decomposition, membership};
+
+    #[test]
+    fn suggests_covered_equivalents() {
+        let decompositions = vec![
+            decomposition("亜", &["｜", "一", "口"]),
+            decomposition("化", &["⺅", "匕"]),
+        ];
+        let memberships = vec![membership("一", 1, &["亜"]), membership("⺅", 2, &["化"])];
+        // A font with every character except the radical blocks and 亜
+        let coverage = Coverage::new(&decompositions, &memberships, |c| {
+            !('\u{2E80}'..='\u{2FDF}').contains(&c) && c != '亜'
+        });
+        assert_eq!(coverage.missing_kanji, vec!["亜"]);
+
+        let person = coverage
+            .missing_radicals
+            .iter()
+            .find(|missing| missing.radical == "⺅")
+            .unwrap();
+        assert_eq!(person.codepoint, "U+2E85");
+        let suggestions: Vec<_> = person
+            .suggestions
+            .iter()
+            .map(|suggestion| (suggestion.glyph.as_str(), suggestion.source))
+            .collect();
+        assert_eq!(
+            suggestions,
+            vec![("亻", Source::Unified), ("化", Source::Original)]
+        );
+        assert!(coverage
+            .missing_radicals
+            .iter()
+            .all(|missing| missing.radical != "一"));
+    }
+
+    #[test]
+    fn checks_static_data_by_default() {
+        let memberships = static_memberships();
+        assert_eq!(memberships.len(), MEMBERSHIPS.len());
+        assert!(memberships
+            .iter()
+            .any(|membership| matches!(membership.radical.alternate, Alternate::Glyph(_))));
+        let coverage = Coverage::new(&static_decompositions(), &memberships, |c| c != '亜');
+        assert_eq!(coverage.radicals, MEMBERSHIPS.len());
+        assert_eq!(coverage.kanji, DECOMPOSITIONS.len());
+        assert_eq!(coverage.missing_kanji, vec!["亜"]);
+    }
+}
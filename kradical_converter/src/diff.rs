@@ -0,0 +1,335 @@
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    fmt::{self, Display, Formatter},
+};
+
+use crate::report::heading;
+use kradical_parsing::{invert, krad::Decomposition, radk::Membership};
+use serde::Serialize;
+
+/// Differences between two releases of the kradfiles
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct KradDiff {
+    /// Kanji only in the newer release
+    pub added: Vec<String>,
+
+    /// Kanji only in the older release
+    pub removed: Vec<String>,
+
+    /// Kanji whose radicals differ between releases
+    pub changed: Vec<DecompositionChange>,
+}
+
+/// A kanji whose radicals differ between releases
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct DecompositionChange {
+    pub kanji: String,
+    pub old: Vec<String>,
+    pub new: Vec<String>,
+}
+
+/// Differences between two releases of the radkfiles
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct RadkDiff {
+    /// Radicals only in the newer release
+    pub added: Vec<String>,
+
+    /// Radicals only in the older release
+    pub removed: Vec<String>,
+
+    /// Radicals whose kanji differ between releases
+    pub memberships: Vec<MembershipChange>,
+
+    /// Radicals whose stroke counts differ between releases
+    pub strokes: Vec<StrokeChange>,
+}
+
+/// A radical whose kanji differ between releases
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct MembershipChange {
+    pub radical: String,
+    pub added: Vec<String>,
+    pub removed: Vec<String>,
+}
+
+/// A radical whose stroke count differs between releases
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct StrokeChange {
+    pub radical: String,
+    pub old: u8,
+    pub new: u8,
+}
+
+/// Disagreements between the kradfiles and the radkfiles
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Drift {
+    /// Radicals used by the kradfiles that the radkfiles do not list
+    pub unknown_radicals: Vec<String>,
+
+    /// The kradfiles compared with decompositions derived from the radkfiles
+    pub krad: KradDiff,
+
+    /// The radkfiles compared with memberships derived from the kradfiles
+    pub radk: RadkDiff,
+}
+
+impl KradDiff {
+    pub fn new(old: &[Decomposition], new: &[Decomposition]) -> Self {
+        let old = by_key(old, |decomposition| &decomposition.kanji);
+        let new = by_key(new, |decomposition| &decomposition.kanji);
+        let changed = old
+            .iter()
+            .filter_map(|(kanji, old)| {
+                let new = new.get(kanji)?;
+                let old_set: BTreeSet<_> = old.radicals.iter().collect();
+                let new_set: BTreeSet<_> = new.radicals.iter().collect();
+                if old_set == new_set {
+                    None
+                } else {
+                    Some(DecompositionChange {
+                        kanji: kanji.to_string(),
+                        old: old.radicals.clone(),
+                        new: new.radicals.clone(),
+                    })
+                }
+            })
+            .collect();
+        Self {
+            added: missing_keys(&new, &old),
+            removed: missing_keys(&old, &new),
+            changed,
+        }
+    }
+}
+
+impl RadkDiff {
+    pub fn new(old: &[Membership], new: &[Membership]) -> Self {
+        let old = by_key(old, |membership| &membership.radical.glyph);
+        let new = by_key(new, |membership| &membership.radical.glyph);
+        let mut memberships = vec![];
+        let mut strokes = vec![];
+        for (radical, old) in old.iter() {
+            let new = match new.get(radical) {
+                Some(new) => new,
+                None => continue,
+            };
+            let old_kanji: BTreeSet<_> = old.kanji.iter().collect();
+            let new_kanji: BTreeSet<_> = new.kanji.iter().collect();
+            if old_kanji != new_kanji {
+                memberships.push(MembershipChange {
+                    radical: radical.to_string(),
+                    added: new_kanji
+                        .difference(&old_kanji)
+                        .map(|s| s.to_string())
+                        .collect(),
+                    removed: old_kanji
+                        .difference(&new_kanji)
+                        .map(|s| s.to_string())
+                        .collect(),
+                });
+            }
+            if old.radical.strokes != new.radical.strokes {
+                strokes.push(StrokeChange {
+                    radical: radical.to_string(),
+                    old: old.radical.strokes,
+                    new: new.radical.strokes,
+                });
+            }
+        }
+        Self {
+            added: missing_keys(&new, &old),
+            removed: missing_keys(&old, &new),
+            memberships,
+            strokes,
+        }
+    }
+}
+
+impl Drift {
+    pub fn new(decompositions: &[Decomposition], memberships: &[Membership]) -> Self {
+        let radicals = invert::radicals(memberships);
+        Self {
+            unknown_radicals: invert::unknown_radicals(decompositions, &radicals),
+            krad: KradDiff::new(decompositions, &invert::decompositions(memberships)),
+            radk: RadkDiff::new(memberships, &invert::memberships(decompositions, &radicals)),
+        }
+    }
+}
+
+/// Indexes items by a key, keeping the first item for repeated keys
+fn by_key<'a, T>(items: &'a [T], key: impl Fn(&'a T) -> &'a str) -> BTreeMap<&'a str, &'a T> {
+    let mut map = BTreeMap::new();
+    for item in items {
+        map.entry(key(item)).or_insert(item);
+    }
+    map
+}
+
+fn missing_keys<T>(from: &BTreeMap<&str, T>, within: &BTreeMap<&str, T>) -> Vec<String> {
+    from.keys()
+        .filter(|key| !within.contains_key(*key))
+        .map(|key| key.to_string())
+        .collect()
+}
+
+impl Display for KradDiff {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        heading(f, "Added kanji", self.added.len())?;
+        list(f, &self.added)?;
+        heading(f, "Removed kanji", self.removed.len())?;
+        list(f, &self.removed)?;
+        heading(f, "Changed decompositions", self.changed.len())?;
+        for change in self.changed.iter() {
+            writeln!(
+                f,
+                "  {} : {} -> {}",
+                change.kanji,
+                change.old.join(" "),
+                change.new.join(" ")
+            )?;
+        }
+        Ok(())
+    }
+}
+
+impl Display for RadkDiff {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        heading(f, "Added radicals", self.added.len())?;
+        list(f, &self.added)?;
+        heading(f, "Removed radicals", self.removed.len())?;
+        list(f, &self.removed)?;
+        heading(f, "Changed memberships", self.memberships.len())?;
+        for change in self.memberships.iter() {
+            let added = change.added.iter().map(|kanji| format!("+{}", kanji));
+            let removed = change.removed.iter().map(|kanji| format!("-{}", kanji));
+            let changes: Vec<_> = added.chain(removed).collect();
+            writeln!(f, "  {} : {}", change.radical, changes.join(" "))?;
+        }
+        heading(f, "Changed stroke counts", self.strokes.len())?;
+        for change in self.strokes.iter() {
+            writeln!(f, "  {} : {} -> {}", change.radical, change.old, change.new)?;
+        }
+        Ok(())
+    }
+}
+
+impl Display for Drift {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        heading(
+            f,
+            "Radicals missing from the radkfiles",
+            self.unknown_radicals.len(),
+        )?;
+        list(f, &self.unknown_radicals)?;
+        writeln!(f)?;
+        writeln!(f, "Decompositions derived from the radkfiles")?;
+        write!(f, "{}", self.krad)?;
+        writeln!(f)?;
+        writeln!(f, "Memberships derived from the kradfiles")?;
+        write!(f, "{}", self.radk)
+    }
+}
+
+fn list(f: &mut Formatter<'_>, items: &[String]) -> fmt::Result {
+    if items.is_empty() {
+        Ok(())
+    } else {
+        writeln!(f, "  {}", items.join(" "))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use kradical_parsing::test_support::{decomposition, membership};
+
+    #[test]
+    fn krad_changes() {
+        let old = [
+            decomposition("亜", &["｜", "一", "口"]),
+            decomposition("唖", &["｜", "一", "口"]),
+            decomposition("娃", &["女", "土"]),
+        ];
+        let new = [
+            decomposition("亜", &["一", "｜", "口"]),
+            decomposition("唖", &["一", "口"]),
+            decomposition("阿", &["一", "口", "亅", "⻖"]),
+        ];
+        let diff = KradDiff::new(&old, &new);
+        assert_eq!(
+            diff,
+            KradDiff {
+                added: vec!["阿".to_string()],
+                removed: vec!["娃".to_string()],
+                changed: vec![DecompositionChange {
+                    kanji: "唖".to_string(),
+                    old: vec!["｜".to_string(), "一".to_string(), "口".to_string()],
+                    new: vec!["一".to_string(), "口".to_string()],
+                }],
+            }
+        );
+    }
+
+    #[test]
+    fn drift_between_families() {
+        let decompositions = [
+            decomposition("亜", &["｜", "一", "口"]),
+            decomposition("悒", &["心", "邑"]),
+        ];
+        let memberships = [
+            membership("一", 1, &["亜"]),
+            membership("｜", 1, &["亜"]),
+            membership("口", 3, &["亜", "唖"]),
+            membership("心", 4, &["悒"]),
+        ];
+        let drift = Drift::new(&decompositions, &memberships);
+        assert_eq!(drift.unknown_radicals, vec!["邑".to_string()]);
+        assert_eq!(drift.krad.added, vec!["唖".to_string()]);
+        assert_eq!(
+            drift.krad.changed,
+            vec![DecompositionChange {
+                kanji: "悒".to_string(),
+                old: vec!["心".to_string(), "邑".to_string()],
+                new: vec!["心".to_string()],
+            }]
+        );
+        assert_eq!(
+            drift.radk.memberships,
+            vec![MembershipChange {
+                radical: "口".to_string(),
+                added: vec![],
+                removed: vec!["唖".to_string()],
+            }]
+        );
+    }
+
+    #[test]
+    fn radk_changes() {
+        let old = [
+            membership("一", 1, &["丁", "七"]),
+            membership("｜", 1, &["中"]),
+        ];
+        let new = [
+            membership("一", 2, &["丁", "三"]),
+            membership("ノ", 1, &["乃"]),
+        ];
+        let diff = RadkDiff::new(&old, &new);
+        assert_eq!(
+            diff,
+            RadkDiff {
+                added: vec!["ノ".to_string()],
+                removed: vec!["｜".to_string()],
+                memberships: vec![MembershipChange {
+                    radical: "一".to_string(),
+                    added: vec!["三".to_string()],
+                    removed: vec!["七".to_string()],
+                }],
+                strokes: vec![StrokeChange {
+                    radical: "一".to_string(),
+                    old: 1,
+                    new: 2,
+                }],
+            }
+        );
+    }
+}
@@ -1,14 +1,182 @@
-use kradical_parsing::{krad::KradError, radk::RadkError};
+use kradical_parsing::{bmp::BmpError, krad::KradError, radk::RadkError};
 use thiserror::Error;
 
+/// Exit codes for each kind of failure, following `sysexits.h`
+pub mod exit {
+    /// The outputs checked against a manifest are out of date
+    pub const STALE: i32 = 1;
+
+    /// The options or the data do not allow the requested conversion
+    pub const VALIDATION: i32 = 64;
+
+    /// An input file could not be parsed
+    pub const PARSE: i32 = 65;
+
+    /// A file could not be read or written
+    pub const IO: i32 = 74;
+}
+
 #[derive(Debug, Error)]
 pub enum ConvertError {
-    #[error("Error during krad parsing")]
-    Krad(#[from] KradError),
+    #[error("Could not read {path}: {source}")]
+    Read {
+        path: String,
+        source: std::io::Error,
+    },
+
+    #[error("Could not write {path}: {source}")]
+    Write {
+        path: String,
+        source: std::io::Error,
+    },
+
+    #[error("Could not parse kradfile {path}: {source}")]
+    Krad { path: String, source: KradError },
+
+    #[error("Could not parse radkfile {path}: {source}")]
+    Radk { path: String, source: RadkError },
+
+    #[error("Could not generate {path}: {source}")]
+    Output { path: String, source: FormatError },
+
+    #[error("Could not serialize the report: {0}")]
+    Report(serde_json::Error),
+
+    #[error("No output format given for {0}")]
+    MissingFormat(String),
+
+    #[error("This command requires --radk-inputs")]
+    MissingRadkInputs,
+
+    #[error("The frequency order requires --kanjidic")]
+    MissingKanjidic,
+
+    #[error("{0} is not one of the --inputs")]
+    NotAnInput(String),
+
+    #[error("Could not read corrections from {path}: {source}")]
+    Overlay { path: String, source: csv::Error },
+
+    #[error("Invalid correction in {path} on line {line}: {reason}")]
+    InvalidCorrection {
+        path: String,
+        line: u64,
+        reason: String,
+    },
+
+    #[error("Only conversion commands produce outputs")]
+    NotAConversion,
+
+    #[error("Could not parse manifest {path}: {source}")]
+    Manifest {
+        path: String,
+        source: toml::de::Error,
+    },
+
+    #[error("Invalid {command} conversion in manifest: {reason}")]
+    InvalidConversion { command: String, reason: String },
+
+    #[error("{0} outputs are out of date")]
+    StaleOutputs(usize),
 
-    #[error("Error during radk parsing")]
-    Radk(#[from] RadkError),
+    #[error("Could not run {program}: {source}")]
+    Spawn {
+        program: String,
+        source: std::io::Error,
+    },
 
-    #[error("IO error")]
+    #[error("rustfmt failed: {0}")]
+    Rustfmt(String),
+
+    #[error("Could not parse KANJIDIC2 file {path}: {source}")]
+    Kanjidic {
+        path: String,
+        source: xml::reader::Error,
+    },
+
+    #[error("Unknown radical {0}")]
+    UnknownRadical(String),
+
+    #[error("Error while replacing characters outside the BMP: {0}")]
+    Bmp(#[from] BmpError),
+
+    #[error("Could not parse font {path}: {source}")]
+    Font {
+        path: String,
+        source: ttf_parser::FaceParsingError,
+    },
+}
+
+/// A failure while formatting an output, before it is written to its path
+#[derive(Debug, Error)]
+pub enum FormatError {
+    #[error("Charts need the stroke counts from radkfile inputs")]
+    ChartWithoutRadk,
+
+    #[error("The anki format is only available for the combined command")]
+    AnkiWithoutCombined,
+
+    #[error("{0} is not a single code point")]
+    NotACodePoint(String),
+
+    #[error("{0}")]
     Io(#[from] std::io::Error),
+
+    #[error("JSON serialization failed: {0}")]
+    Json(#[from] serde_json::Error),
+
+    #[error("CSV serialization failed: {0}")]
+    Csv(#[from] csv::Error),
+
+    #[error("Could not build the SQLite database: {0}")]
+    Sqlite(#[from] rusqlite::Error),
+
+    #[error("Could not build the ZIP archive: {0}")]
+    Zip(#[from] zip::result::ZipError),
+
+    #[error("Could not build the binary data: {0}")]
+    Binary(#[from] kradical_binary::BinaryError),
+}
+
+impl ConvertError {
+    /// The process exit code for the kind of failure
+    pub fn exit_code(&self) -> i32 {
+        match self {
+            Self::StaleOutputs(_) => exit::STALE,
+            Self::Krad { .. }
+            | Self::Radk { .. }
+            | Self::Overlay { .. }
+            | Self::InvalidCorrection { .. }
+            | Self::Manifest { .. }
+            | Self::Kanjidic { .. }
+            | Self::Font { .. } => exit::PARSE,
+            Self::Output { source, .. } => source.exit_code(),
+            Self::Read { .. }
+            | Self::Write { .. }
+            | Self::Report(_)
+            | Self::Spawn { .. }
+            | Self::Rustfmt(_) => exit::IO,
+            Self::MissingFormat(_)
+            | Self::MissingRadkInputs
+            | Self::MissingKanjidic
+            | Self::NotAnInput(_)
+            | Self::NotAConversion
+            | Self::InvalidConversion { .. }
+            | Self::UnknownRadical(_)
+            | Self::Bmp(_) => exit::VALIDATION,
+        }
+    }
+}
+
+impl FormatError {
+    /// The process exit code for the kind of failure
+    pub fn exit_code(&self) -> i32 {
+        match self {
+            Self::ChartWithoutRadk
+            | Self::AnkiWithoutCombined
+            | Self::NotACodePoint(_)
+            | Self::Binary(_) => exit::VALIDATION,
+            Self::Io(_) | Self::Json(_) | Self::Csv(_) | Self::Sqlite(_) | Self::Zip(_) => exit::IO,
+        }
+    }
 }
@@ -0,0 +1,46 @@
+use std::{
+    fs::OpenOptions,
+    io::{self, ErrorKind, Read, Write},
+};
+
+use crate::error::ConvertError;
+
+/// The path used to stand for stdin or stdout
+const STANDARD_STREAM: &str = "-";
+
+/// Reads the contents of a file, or stdin if the path is `-`
+pub fn read(path: &str) -> Result<Vec<u8>, ConvertError> {
+    let read = if path == STANDARD_STREAM {
+        let mut bytes = vec![];
+        io::stdin().lock().read_to_end(&mut bytes).map(|_| bytes)
+    } else {
+        std::fs::read(path)
+    };
+    read.map_err(|source| ConvertError::Read {
+        path: path.to_string(),
+        source,
+    })
+}
+
+/// Replaces the contents of a file, or writes to stdout if the path is `-`.
+/// Stdout closing early, as when piped to `head`, is not an error.
+pub fn write(path: &str, bytes: &[u8]) -> Result<(), ConvertError> {
+    let written = if path == STANDARD_STREAM {
+        let mut stdout = io::stdout().lock();
+        match stdout.write_all(bytes).and_then(|_| stdout.flush()) {
+            Err(err) if err.kind() == ErrorKind::BrokenPipe => Ok(()),
+            written => written,
+        }
+    } else {
+        OpenOptions::new()
+            .write(true)
+            .create(true)
+            .truncate(true)
+            .open(path)
+            .and_then(|mut file| file.write_all(bytes))
+    };
+    written.map_err(|source| ConvertError::Write {
+        path: path.to_string(),
+        source,
+    })
+}
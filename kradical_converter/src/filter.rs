@@ -0,0 +1,204 @@
+use std::{
+    collections::{HashMap, HashSet},
+    sync::OnceLock,
+};
+
+use crate::{
+    error::ConvertError,
+    files, krad,
+    opts::{CodepointRange, ConvertOpts, InputFormat},
+    radk,
+};
+use kradical_jis::jis213_to_utf8;
+use kradical_parsing::{krad::Decomposition, radk::Membership};
+
+/// The first row of each JIS X 0208 kanji level
+const LEVEL_ROWS: [u8; 2] = [16, 48];
+
+/// The last row of JIS X 0208 kanji
+const LAST_ROW: u8 = 84;
+
+/// The last JIS X 0208 cell of the rows that JIS X 0213 fills out
+const LAST_CELLS: [(u8, u8); 2] = [(47, 51), (84, 6)];
+
+/// Criteria that each kept kanji must meet
+#[derive(Debug, Default)]
+pub struct Filter {
+    /// Kanji listed in the chosen input files
+    pub sources: Option<HashSet<String>>,
+
+    /// Characters from a kanji list file
+    pub list: Option<HashSet<char>>,
+
+    /// The highest JIS X 0208 level to keep
+    pub jis_level: Option<u8>,
+
+    /// Code point ranges to keep
+    pub ranges: Vec<CodepointRange>,
+}
+
+impl Filter {
+    /// Gathers the criteria from the options, where `inputs` is the
+    /// file family of `--inputs`
+    pub fn new(opts: &ConvertOpts, inputs: InputFormat) -> Result<Self, ConvertError> {
+        let mut sources = None;
+        if !opts.from.is_empty() {
+            let mut kanji = HashSet::new();
+            for path in opts.from.iter() {
+                if !opts.inputs.contains(path) {
+                    return Err(ConvertError::NotAnInput(path.clone()));
+                }
+                let path = std::slice::from_ref(path);
+                match inputs {
+                    InputFormat::Krad => kanji.extend(
+                        krad::parse(path)?
+                            .into_iter()
+                            .map(|decomposition| decomposition.kanji),
+                    ),
+                    InputFormat::Radk => kanji.extend(
+                        radk::parse(path)?
+                            .into_iter()
+                            .flat_map(|membership| membership.kanji),
+                    ),
+                }
+            }
+            sources = Some(kanji);
+        }
+        let list = match &opts.kanji_list {
+            Some(path) => Some(parse_list(&String::from_utf8_lossy(&files::read(path)?))),
+            None => None,
+        };
+        Ok(Self {
+            sources,
+            list,
+            jis_level: opts.jis_level,
+            ranges: opts.codepoints.clone(),
+        })
+    }
+
+    /// Whether any criteria were given
+    pub fn is_active(&self) -> bool {
+        self.sources.is_some()
+            || self.list.is_some()
+            || self.jis_level.is_some()
+            || !self.ranges.is_empty()
+    }
+
+    pub fn keeps(&self, kanji: &str) -> bool {
+        let in_sources = match &self.sources {
+            Some(sources) => sources.contains(kanji),
+            None => true,
+        };
+        in_sources
+            && kanji.chars().all(|c| {
+                let in_list = match &self.list {
+                    Some(list) => list.contains(&c),
+                    None => true,
+                };
+                let in_level = match self.jis_level {
+                    Some(level) => jis_level(c).is_some_and(|found| found <= level),
+                    None => true,
+                };
+                let in_ranges =
+                    self.ranges.is_empty() || self.ranges.iter().any(|range| range.contains(c));
+                in_list && in_level && in_ranges
+            })
+    }
+
+    pub fn decompositions(&self, decompositions: &mut Vec<Decomposition>) {
+        if self.is_active() {
+            decompositions.retain(|decomposition| self.keeps(&decomposition.kanji));
+        }
+    }
+
+    /// Removes filtered kanji from the memberships, along with any radicals
+    /// left without kanji
+    pub fn memberships(&self, memberships: &mut Vec<Membership>) {
+        if self.is_active() {
+            for membership in memberships.iter_mut() {
+                membership.kanji.retain(|kanji| self.keeps(kanji));
+            }
+            memberships.retain(|membership| !membership.kanji.is_empty());
+        }
+    }
+}
+
+/// Reads a kanji list, where every character other than whitespace is a
+/// kanji and lines starting with `#` are comments
+pub fn parse_list(text: &str) -> HashSet<char> {
+    text.lines()
+        .filter(|line| !line.trim_start().starts_with('#'))
+        .flat_map(|line| line.chars())
+        .filter(|c| !c.is_whitespace())
+        .collect()
+}
+
+/// The JIS X 0208 level of a kanji, or `None` if it is not a
+/// JIS X 0208 kanji
+pub fn jis_level(kanji: char) -> Option<u8> {
+    static LEVELS: OnceLock<HashMap<char, u8>> = OnceLock::new();
+    LEVELS.get_or_init(jis_levels).get(&kanji).copied()
+}
+
+/// The level of each JIS X 0208 kanji, read from the JIS X 0213 table
+/// that extends it
+fn jis_levels() -> HashMap<char, u8> {
+    let mut levels = HashMap::new();
+    for row in LEVEL_ROWS[0]..=LAST_ROW {
+        let level = if row >= LEVEL_ROWS[1] { 2 } else { 1 };
+        let last_cell = LAST_CELLS
+            .iter()
+            .find(|(last_row, _)| *last_row == row)
+            .map_or(94, |(_, cell)| *cell);
+        for cell in 1..=last_cell {
+            // EUC-JP bytes, as the table is keyed
+            let code = (row as u32 + 0xA0) << 8 | (cell as u32 + 0xA0);
+            let mut chars = jis213_to_utf8(code).unwrap_or_default().chars();
+            if let (Some(kanji), None) = (chars.next(), chars.next()) {
+                levels.insert(kanji, level);
+            }
+        }
+    }
+    levels
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use kradical_parsing::test_support::membership;
+
+    #[test]
+    fn finds_jis_levels() {
+        assert_eq!(jis_level('亜'), Some(1));
+        assert_eq!(jis_level('腕'), Some(1));
+        assert_eq!(jis_level('弌'), Some(2));
+        assert_eq!(jis_level('丂'), None);
+        assert_eq!(jis_level('あ'), None);
+    }
+
+    #[test]
+    fn drops_empty_radicals() {
+        let filter = Filter {
+            list: Some(parse_list("# Kanji to keep\n亜 唖\n")),
+            ..Filter::default()
+        };
+        let mut memberships = vec![
+            membership("一", 1, &["亜", "丁"]),
+            membership("亅", 1, &["丁"]),
+        ];
+        filter.memberships(&mut memberships);
+        assert_eq!(memberships, vec![membership("一", 1, &["亜"])]);
+    }
+
+    #[test]
+    fn combines_criteria() {
+        let filter = Filter {
+            jis_level: Some(1),
+            ranges: vec!["U+4E00-U+4FFF".parse().unwrap()],
+            ..Filter::default()
+        };
+        assert!(filter.keeps("亜"));
+        assert!(!filter.keeps("唖"));
+        assert!(!filter.keeps("丂"));
+    }
+}
@@ -0,0 +1,597 @@
+use std::{collections::HashMap, io::Write};
+
+use crate::{
+    binary, chart,
+    combined::{self, Dataset},
+    error::FormatError,
+    json::JsonStyle,
+    krad,
+    opts::{FormatOptions, GraphKind, Layout, OutputFormat},
+    radk, sqlite,
+    table::Delimiter,
+    yomitan,
+};
+use kradical_parsing::{invert, krad::Decomposition, radk::Membership};
+
+/// Writes converted files in one output format
+pub trait Formatter {
+    /// Writes the radicals of each kanji, as from a kradfile
+    fn decompositions(
+        &self,
+        decompositions: &[Decomposition],
+        out: &mut dyn Write,
+    ) -> Result<(), FormatError>;
+
+    /// Writes the kanji containing each radical, as from a radkfile
+    fn memberships(
+        &self,
+        memberships: &[Membership],
+        out: &mut dyn Write,
+    ) -> Result<(), FormatError>;
+
+    /// Writes both file families together
+    fn dataset(&self, dataset: &Dataset, out: &mut dyn Write) -> Result<(), FormatError>;
+
+    /// Writes decompositions along with any radkfile memberships that were
+    /// loaded for them, for formats that draw on the memberships instead
+    fn decompositions_with_radk(
+        &self,
+        decompositions: &[Decomposition],
+        _memberships: Option<&[Membership]>,
+        out: &mut dyn Write,
+    ) -> Result<(), FormatError> {
+        self.decompositions(decompositions, out)
+    }
+}
+
+/// The formatter for an output format and its options
+pub fn formatter<'a>(format: OutputFormat, options: FormatOptions<'a>) -> Box<dyn Formatter + 'a> {
+    match format {
+        OutputFormat::Unicode => Box::new(Unicode),
+        OutputFormat::Rust => Box::new(Rust),
+        OutputFormat::Typescript => Box::new(Typescript),
+        OutputFormat::Python => Box::new(Python),
+        OutputFormat::C => Box::new(C),
+        OutputFormat::Json => Box::new(Json {
+            style: options.json_style,
+        }),
+        OutputFormat::Ndjson => Box::new(Ndjson),
+        OutputFormat::Csv => Box::new(Table {
+            layout: options.layout,
+            delimiter: Delimiter::Comma,
+        }),
+        OutputFormat::Tsv => Box::new(Table {
+            layout: options.layout,
+            delimiter: Delimiter::Tab,
+        }),
+        OutputFormat::Sqlite => Box::new(Sqlite),
+        OutputFormat::Binary => Box::new(Binary),
+        OutputFormat::Html => Box::new(Html {
+            image_url: options.image_url,
+        }),
+        OutputFormat::Svg => Box::new(Svg {
+            image_url: options.image_url,
+        }),
+        OutputFormat::Anki => Box::new(Anki),
+        OutputFormat::Yomitan => Box::new(Yomitan),
+        OutputFormat::Dot => Box::new(Dot {
+            kind: options.graph,
+            subset: options.subset,
+        }),
+        OutputFormat::Graphml => Box::new(Graphml {
+            kind: options.graph,
+            subset: options.subset,
+        }),
+    }
+}
+
+/// The lines of the original files with radicals and kanji in UTF-8
+pub struct Unicode;
+
+impl Formatter for Unicode {
+    fn decompositions(
+        &self,
+        decompositions: &[Decomposition],
+        out: &mut dyn Write,
+    ) -> Result<(), FormatError> {
+        Ok(krad::write_unicode(decompositions, out)?)
+    }
+
+    fn memberships(
+        &self,
+        memberships: &[Membership],
+        out: &mut dyn Write,
+    ) -> Result<(), FormatError> {
+        Ok(radk::write_unicode(memberships, out)?)
+    }
+
+    fn dataset(&self, dataset: &Dataset, out: &mut dyn Write) -> Result<(), FormatError> {
+        Ok(combined::write_unicode(dataset, out)?)
+    }
+}
+
+/// Constants for the `kradical_static` crate
+pub struct Rust;
+
+impl Formatter for Rust {
+    fn decompositions(
+        &self,
+        decompositions: &[Decomposition],
+        out: &mut dyn Write,
+    ) -> Result<(), FormatError> {
+        Ok(krad::write_rust(decompositions, out)?)
+    }
+
+    fn memberships(
+        &self,
+        memberships: &[Membership],
+        out: &mut dyn Write,
+    ) -> Result<(), FormatError> {
+        Ok(radk::write_rust(memberships, out)?)
+    }
+
+    fn dataset(&self, dataset: &Dataset, out: &mut dyn Write) -> Result<(), FormatError> {
+        Ok(combined::write_rust(dataset, out)?)
+    }
+}
+
+/// A TypeScript module with readonly types
+pub struct Typescript;
+
+impl Formatter for Typescript {
+    fn decompositions(
+        &self,
+        decompositions: &[Decomposition],
+        out: &mut dyn Write,
+    ) -> Result<(), FormatError> {
+        Ok(krad::write_typescript(decompositions, out)?)
+    }
+
+    fn memberships(
+        &self,
+        memberships: &[Membership],
+        out: &mut dyn Write,
+    ) -> Result<(), FormatError> {
+        Ok(radk::write_typescript(memberships, out)?)
+    }
+
+    fn dataset(&self, dataset: &Dataset, out: &mut dyn Write) -> Result<(), FormatError> {
+        Ok(combined::write_code(
+            dataset,
+            krad::write_typescript,
+            radk::write_typescript,
+            out,
+        )?)
+    }
+}
+
+/// A Python module of named tuples
+pub struct Python;
+
+impl Formatter for Python {
+    fn decompositions(
+        &self,
+        decompositions: &[Decomposition],
+        out: &mut dyn Write,
+    ) -> Result<(), FormatError> {
+        Ok(krad::write_python(decompositions, out)?)
+    }
+
+    fn memberships(
+        &self,
+        memberships: &[Membership],
+        out: &mut dyn Write,
+    ) -> Result<(), FormatError> {
+        Ok(radk::write_python(memberships, out)?)
+    }
+
+    fn dataset(&self, dataset: &Dataset, out: &mut dyn Write) -> Result<(), FormatError> {
+        Ok(combined::write_code(
+            dataset,
+            krad::write_python,
+            radk::write_python,
+            out,
+        )?)
+    }
+}
+
+/// A C header of static arrays
+pub struct C;
+
+impl Formatter for C {
+    fn decompositions(
+        &self,
+        decompositions: &[Decomposition],
+        out: &mut dyn Write,
+    ) -> Result<(), FormatError> {
+        Ok(krad::write_c(decompositions, out)?)
+    }
+
+    fn memberships(
+        &self,
+        memberships: &[Membership],
+        out: &mut dyn Write,
+    ) -> Result<(), FormatError> {
+        Ok(radk::write_c(memberships, out)?)
+    }
+
+    fn dataset(&self, dataset: &Dataset, out: &mut dyn Write) -> Result<(), FormatError> {
+        Ok(combined::write_code(
+            dataset,
+            krad::write_c,
+            radk::write_c,
+            out,
+        )?)
+    }
+}
+
+/// A single JSON document
+pub struct Json {
+    pub style: JsonStyle,
+}
+
+impl Formatter for Json {
+    fn decompositions(
+        &self,
+        decompositions: &[Decomposition],
+        out: &mut dyn Write,
+    ) -> Result<(), FormatError> {
+        Ok(krad::write_json(decompositions, self.style, out)?)
+    }
+
+    fn memberships(
+        &self,
+        memberships: &[Membership],
+        out: &mut dyn Write,
+    ) -> Result<(), FormatError> {
+        Ok(radk::write_json(memberships, self.style, out)?)
+    }
+
+    fn dataset(&self, dataset: &Dataset, out: &mut dyn Write) -> Result<(), FormatError> {
+        Ok(combined::write_json(dataset, self.style, out)?)
+    }
+}
+
+/// One JSON document per line
+pub struct Ndjson;
+
+impl Formatter for Ndjson {
+    fn decompositions(
+        &self,
+        decompositions: &[Decomposition],
+        out: &mut dyn Write,
+    ) -> Result<(), FormatError> {
+        Ok(krad::write_ndjson(decompositions, out)?)
+    }
+
+    fn memberships(
+        &self,
+        memberships: &[Membership],
+        out: &mut dyn Write,
+    ) -> Result<(), FormatError> {
+        Ok(radk::write_ndjson(memberships, out)?)
+    }
+
+    fn dataset(&self, dataset: &Dataset, out: &mut dyn Write) -> Result<(), FormatError> {
+        Ok(combined::write_ndjson(dataset, out)?)
+    }
+}
+
+/// Comma- or tab-separated values with a header row
+pub struct Table {
+    pub layout: Layout,
+    pub delimiter: Delimiter,
+}
+
+impl Formatter for Table {
+    fn decompositions(
+        &self,
+        decompositions: &[Decomposition],
+        out: &mut dyn Write,
+    ) -> Result<(), FormatError> {
+        Ok(krad::write_table(
+            decompositions,
+            self.layout,
+            self.delimiter,
+            out,
+        )?)
+    }
+
+    fn memberships(
+        &self,
+        memberships: &[Membership],
+        out: &mut dyn Write,
+    ) -> Result<(), FormatError> {
+        Ok(radk::write_table(
+            memberships,
+            self.layout,
+            self.delimiter,
+            out,
+        )?)
+    }
+
+    fn dataset(&self, dataset: &Dataset, out: &mut dyn Write) -> Result<(), FormatError> {
+        Ok(combined::write_table(
+            dataset,
+            self.layout,
+            self.delimiter,
+            out,
+        )?)
+    }
+}
+
+/// A SQLite database file
+pub struct Sqlite;
+
+impl Formatter for Sqlite {
+    fn decompositions(
+        &self,
+        decompositions: &[Decomposition],
+        out: &mut dyn Write,
+    ) -> Result<(), FormatError> {
+        Ok(out.write_all(&sqlite::from_decompositions(decompositions)?)?)
+    }
+
+    fn memberships(
+        &self,
+        memberships: &[Membership],
+        out: &mut dyn Write,
+    ) -> Result<(), FormatError> {
+        Ok(out.write_all(&sqlite::from_memberships(memberships)?)?)
+    }
+
+    fn dataset(&self, dataset: &Dataset, out: &mut dyn Write) -> Result<(), FormatError> {
+        let bytes = sqlite::from_dataset(&dataset.decompositions, &dataset.memberships)?;
+        Ok(out.write_all(&bytes)?)
+    }
+}
+
+/// The `kradical_binary` format
+pub struct Binary;
+
+impl Formatter for Binary {
+    fn decompositions(
+        &self,
+        decompositions: &[Decomposition],
+        out: &mut dyn Write,
+    ) -> Result<(), FormatError> {
+        Ok(out.write_all(&binary::from_decompositions(decompositions)?)?)
+    }
+
+    fn memberships(
+        &self,
+        memberships: &[Membership],
+        out: &mut dyn Write,
+    ) -> Result<(), FormatError> {
+        Ok(out.write_all(&binary::from_memberships(memberships)?)?)
+    }
+
+    fn dataset(&self, dataset: &Dataset, out: &mut dyn Write) -> Result<(), FormatError> {
+        let bytes = binary::from_dataset(&dataset.decompositions, &dataset.memberships)?;
+        Ok(out.write_all(&bytes)?)
+    }
+}
+
+/// An HTML page of the radicals by stroke count
+pub struct Html<'a> {
+    /// The URL of alternate images, where `{}` is the image name
+    pub image_url: Option<&'a str>,
+}
+
+impl Formatter for Html<'_> {
+    fn decompositions(&self, _: &[Decomposition], _: &mut dyn Write) -> Result<(), FormatError> {
+        Err(FormatError::ChartWithoutRadk)
+    }
+
+    fn memberships(
+        &self,
+        memberships: &[Membership],
+        out: &mut dyn Write,
+    ) -> Result<(), FormatError> {
+        Ok(chart::write_html(memberships, self.image_url, out)?)
+    }
+
+    fn dataset(&self, dataset: &Dataset, out: &mut dyn Write) -> Result<(), FormatError> {
+        self.memberships(&dataset.memberships, out)
+    }
+
+    fn decompositions_with_radk(
+        &self,
+        _: &[Decomposition],
+        memberships: Option<&[Membership]>,
+        out: &mut dyn Write,
+    ) -> Result<(), FormatError> {
+        match memberships {
+            Some(memberships) => self.memberships(memberships, out),
+            None => Err(FormatError::ChartWithoutRadk),
+        }
+    }
+}
+
+/// An SVG grid of the radicals by stroke count
+pub struct Svg<'a> {
+    /// The URL of alternate images, where `{}` is the image name
+    pub image_url: Option<&'a str>,
+}
+
+impl Formatter for Svg<'_> {
+    fn decompositions(&self, _: &[Decomposition], _: &mut dyn Write) -> Result<(), FormatError> {
+        Err(FormatError::ChartWithoutRadk)
+    }
+
+    fn memberships(
+        &self,
+        memberships: &[Membership],
+        out: &mut dyn Write,
+    ) -> Result<(), FormatError> {
+        Ok(chart::write_svg(memberships, self.image_url, out)?)
+    }
+
+    fn dataset(&self, dataset: &Dataset, out: &mut dyn Write) -> Result<(), FormatError> {
+        self.memberships(&dataset.memberships, out)
+    }
+
+    fn decompositions_with_radk(
+        &self,
+        _: &[Decomposition],
+        memberships: Option<&[Membership]>,
+        out: &mut dyn Write,
+    ) -> Result<(), FormatError> {
+        match memberships {
+            Some(memberships) => self.memberships(memberships, out),
+            None => Err(FormatError::ChartWithoutRadk),
+        }
+    }
+}
+
+/// Notes for import into Anki
+pub struct Anki;
+
+impl Formatter for Anki {
+    fn decompositions(&self, _: &[Decomposition], _: &mut dyn Write) -> Result<(), FormatError> {
+        Err(FormatError::AnkiWithoutCombined)
+    }
+
+    fn memberships(&self, _: &[Membership], _: &mut dyn Write) -> Result<(), FormatError> {
+        Err(FormatError::AnkiWithoutCombined)
+    }
+
+    fn dataset(&self, dataset: &Dataset, out: &mut dyn Write) -> Result<(), FormatError> {
+        Ok(combined::write_anki(dataset, out)?)
+    }
+}
+
+/// A Yomitan dictionary archive
+pub struct Yomitan;
+
+impl Formatter for Yomitan {
+    fn decompositions(
+        &self,
+        decompositions: &[Decomposition],
+        out: &mut dyn Write,
+    ) -> Result<(), FormatError> {
+        Ok(out.write_all(&yomitan::to_archive(decompositions)?)?)
+    }
+
+    fn memberships(
+        &self,
+        memberships: &[Membership],
+        out: &mut dyn Write,
+    ) -> Result<(), FormatError> {
+        self.decompositions(&invert::decompositions(memberships), out)
+    }
+
+    fn dataset(&self, dataset: &Dataset, out: &mut dyn Write) -> Result<(), FormatError> {
+        self.decompositions(&dataset.decompositions, out)
+    }
+}
+
+/// A Graphviz graph
+pub struct Dot<'a> {
+    pub kind: GraphKind,
+
+    /// The kanji to keep, or all of them if `None`
+    pub subset: Option<&'a str>,
+}
+
+impl Formatter for Dot<'_> {
+    fn decompositions(
+        &self,
+        decompositions: &[Decomposition],
+        out: &mut dyn Write,
+    ) -> Result<(), FormatError> {
+        let graph = krad::graph(decompositions, &HashMap::new(), self.subset);
+        Ok(graph.write_dot(self.kind, out)?)
+    }
+
+    fn memberships(
+        &self,
+        memberships: &[Membership],
+        out: &mut dyn Write,
+    ) -> Result<(), FormatError> {
+        let graph = radk::graph(memberships, self.subset);
+        Ok(graph.write_dot(self.kind, out)?)
+    }
+
+    fn dataset(&self, dataset: &Dataset, out: &mut dyn Write) -> Result<(), FormatError> {
+        let details = radk::details(&dataset.memberships);
+        let graph = krad::graph(&dataset.decompositions, &details, self.subset);
+        Ok(graph.write_dot(self.kind, out)?)
+    }
+}
+
+/// A GraphML graph
+pub struct Graphml<'a> {
+    pub kind: GraphKind,
+
+    /// The kanji to keep, or all of them if `None`
+    pub subset: Option<&'a str>,
+}
+
+impl Formatter for Graphml<'_> {
+    fn decompositions(
+        &self,
+        decompositions: &[Decomposition],
+        out: &mut dyn Write,
+    ) -> Result<(), FormatError> {
+        let graph = krad::graph(decompositions, &HashMap::new(), self.subset);
+        Ok(graph.write_graphml(self.kind, out)?)
+    }
+
+    fn memberships(
+        &self,
+        memberships: &[Membership],
+        out: &mut dyn Write,
+    ) -> Result<(), FormatError> {
+        let graph = radk::graph(memberships, self.subset);
+        Ok(graph.write_graphml(self.kind, out)?)
+    }
+
+    fn dataset(&self, dataset: &Dataset, out: &mut dyn Write) -> Result<(), FormatError> {
+        let details = radk::details(&dataset.memberships);
+        let graph = krad::graph(&dataset.decompositions, &details, self.subset);
+        Ok(graph.write_graphml(self.kind, out)?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use kradical_parsing::test_support::{decomposition, membership};
+
+    fn dataset() -> Dataset {
+        Dataset {
+            decompositions: vec![decomposition("亜", &["一", "口"])],
+            memberships: vec![membership("一", 1, &["亜"])],
+            meanings: Default::default(),
+            frequencies: None,
+        }
+    }
+
+    #[test]
+    fn writes_both_families() {
+        let dataset = dataset();
+        let mut out = vec![];
+        Unicode.dataset(&dataset, &mut out).unwrap();
+        assert_eq!(out, "亜 : 一 口\n\n一 1 : 亜".as_bytes());
+    }
+
+    #[test]
+    fn charts_need_memberships() {
+        let dataset = dataset();
+        let html = Html { image_url: None };
+        let res = html.decompositions(&dataset.decompositions, &mut vec![]);
+        assert!(matches!(res, Err(FormatError::ChartWithoutRadk)));
+        let mut out = vec![];
+        html.dataset(&dataset, &mut out).unwrap();
+        assert!(out.starts_with(b"<!DOCTYPE html>"));
+        let mut drawn = vec![];
+        html.decompositions_with_radk(
+            &dataset.decompositions,
+            Some(&dataset.memberships),
+            &mut drawn,
+        )
+        .unwrap();
+        assert_eq!(drawn, out);
+    }
+}
@@ -0,0 +1,40 @@
+use kradical_parsing::{krad::Decomposition, radk::Membership};
+use kradical_static::{normalize, Repertoire};
+
+/// Writes the radicals of each decomposition in a repertoire
+pub fn decompositions(decompositions: &mut [Decomposition], repertoire: Repertoire) {
+    for decomposition in decompositions.iter_mut() {
+        for radical in decomposition.radicals.iter_mut() {
+            *radical = glyph(radical, repertoire);
+        }
+    }
+}
+
+/// Writes the radical of each membership in a repertoire
+pub fn memberships(memberships: &mut [Membership], repertoire: Repertoire) {
+    for membership in memberships.iter_mut() {
+        membership.radical.glyph = glyph(&membership.radical.glyph, repertoire);
+    }
+}
+
+/// Writes each character of a radical in a repertoire
+pub fn glyph(radical: &str, repertoire: Repertoire) -> String {
+    radical.chars().map(|c| normalize(c, repertoire)).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use kradical_parsing::test_support::decomposition;
+
+    #[test]
+    fn normalizes_radicals() {
+        let mut decompositions = vec![decomposition("化", &["⺅", "匕"])];
+        super::decompositions(&mut decompositions, Repertoire::Unified);
+        assert_eq!(decompositions[0].radicals, vec!["亻", "匕"]);
+        super::decompositions(&mut decompositions, Repertoire::Radicals);
+        assert_eq!(decompositions[0].radicals, vec!["⺅", "⼔"]);
+        super::decompositions(&mut decompositions, Repertoire::Original);
+        assert_eq!(decompositions[0].radicals, vec!["化", "匕"]);
+    }
+}
@@ -0,0 +1,299 @@
+use std::{
+    collections::{BTreeMap, HashMap, HashSet},
+    io::{self, Write},
+};
+
+use crate::{codegen::write_lines, opts::GraphKind};
+use kradical_parsing::radk::{Alternate, Radical};
+
+/// The bipartite graph between kanji and the radicals they contain
+pub struct Graph<'a> {
+    /// Radical glyphs with their details, when known
+    radicals: Vec<(&'a str, Option<&'a Radical>)>,
+    kanji: Vec<&'a str>,
+
+    /// Pairs of indices into `kanji` and `radicals`
+    edges: Vec<(usize, usize)>,
+}
+
+/// A node ready to be written
+struct Node<'a> {
+    id: String,
+    kind: &'static str,
+    glyph: &'a str,
+    strokes: Option<u8>,
+    alternate: Option<(&'static str, &'a str)>,
+}
+
+/// An edge ready to be written, weighted by the number of shared kanji
+/// in the co-occurrence graph
+struct Edge {
+    source: String,
+    target: String,
+    weight: Option<usize>,
+}
+
+impl<'a> Graph<'a> {
+    /// Builds the graph from kanji and radical pairs
+    ///
+    /// # Arguments
+    ///
+    /// * `pairs` - Kanji paired with a radical they contain
+    /// * `details` - Stroke counts and alternates by radical glyph
+    /// * `subset` - The kanji to keep, or all of them if `None`
+    pub fn new(
+        pairs: impl IntoIterator<Item = (&'a str, &'a str)>,
+        details: &HashMap<&str, &'a Radical>,
+        subset: Option<&str>,
+    ) -> Self {
+        let subset: Option<HashSet<_>> = subset.map(|subset| subset.chars().collect());
+        let mut graph = Self {
+            radicals: vec![],
+            kanji: vec![],
+            edges: vec![],
+        };
+        let mut radical_ids = HashMap::new();
+        let mut kanji_ids = HashMap::new();
+        let mut seen = HashSet::new();
+        for (kanji, radical) in pairs {
+            let included = match &subset {
+                Some(subset) => kanji.chars().all(|c| subset.contains(&c)),
+                None => true,
+            };
+            if !included {
+                continue;
+            }
+            let kanji_id = *kanji_ids.entry(kanji).or_insert_with(|| {
+                graph.kanji.push(kanji);
+                graph.kanji.len() - 1
+            });
+            let radical_id = *radical_ids.entry(radical).or_insert_with(|| {
+                graph
+                    .radicals
+                    .push((radical, details.get(radical).copied()));
+                graph.radicals.len() - 1
+            });
+            if seen.insert((kanji_id, radical_id)) {
+                graph.edges.push((kanji_id, radical_id));
+            }
+        }
+        graph
+    }
+
+    pub fn write_dot(&self, kind: GraphKind, out: &mut dyn Write) -> io::Result<()> {
+        let (nodes, edges) = self.elements(kind);
+        write_lines(
+            out,
+            &["graph kradical {", "\tnode [fontname=\"sans-serif\"];"],
+        )?;
+        for node in nodes {
+            let mut attributes = vec![
+                format!("label={}", dot_string(node.glyph)),
+                format!("type={}", node.kind),
+            ];
+            if node.kind == "radical" {
+                attributes.push("shape=box".to_string());
+            }
+            if let Some(strokes) = node.strokes {
+                attributes.push(format!("strokes={}", strokes));
+            }
+            if let Some((alternate_type, alternate)) = node.alternate {
+                attributes.push(format!("alternate_type={}", alternate_type));
+                attributes.push(format!("alternate={}", dot_string(alternate)));
+            }
+            writeln!(out, "\t{} [{}];", node.id, attributes.join(", "))?;
+        }
+        for edge in edges {
+            match edge.weight {
+                Some(weight) => writeln!(
+                    out,
+                    "\t{} -- {} [weight={}];",
+                    edge.source, edge.target, weight
+                )?,
+                None => writeln!(out, "\t{} -- {};", edge.source, edge.target)?,
+            }
+        }
+        writeln!(out, "}}")
+    }
+
+    pub fn write_graphml(&self, kind: GraphKind, out: &mut dyn Write) -> io::Result<()> {
+        let (nodes, edges) = self.elements(kind);
+        write_lines(
+            out,
+            &[
+                "<?xml version=\"1.0\" encoding=\"UTF-8\"?>",
+                "<graphml xmlns=\"http://graphml.graphdrawing.org/xmlns\">",
+                "<key id=\"type\" for=\"node\" attr.name=\"type\" attr.type=\"string\"/>",
+                "<key id=\"glyph\" for=\"node\" attr.name=\"glyph\" attr.type=\"string\"/>",
+                "<key id=\"strokes\" for=\"node\" attr.name=\"strokes\" attr.type=\"int\"/>",
+                "<key id=\"alternate_type\" for=\"node\" attr.name=\"alternate_type\" attr.type=\"string\"/>",
+                "<key id=\"alternate\" for=\"node\" attr.name=\"alternate\" attr.type=\"string\"/>",
+                "<key id=\"weight\" for=\"edge\" attr.name=\"weight\" attr.type=\"int\"/>",
+                "<graph id=\"kradical\" edgedefault=\"undirected\">",
+            ],
+        )?;
+        for node in nodes {
+            let mut data = vec![
+                graphml_data("type", node.kind),
+                graphml_data("glyph", node.glyph),
+            ];
+            if let Some(strokes) = node.strokes {
+                data.push(graphml_data("strokes", &strokes.to_string()));
+            }
+            if let Some((alternate_type, alternate)) = node.alternate {
+                data.push(graphml_data("alternate_type", alternate_type));
+                data.push(graphml_data("alternate", alternate));
+            }
+            writeln!(out, "<node id=\"{}\">{}</node>", node.id, data.join(""))?;
+        }
+        for edge in edges {
+            match edge.weight {
+                Some(weight) => writeln!(
+                    out,
+                    "<edge source=\"{}\" target=\"{}\">{}</edge>",
+                    edge.source,
+                    edge.target,
+                    graphml_data("weight", &weight.to_string())
+                )?,
+                None => writeln!(
+                    out,
+                    "<edge source=\"{}\" target=\"{}\"/>",
+                    edge.source, edge.target
+                )?,
+            }
+        }
+        write_lines(out, &["</graph>", "</graphml>"])
+    }
+
+    fn elements(&self, kind: GraphKind) -> (Vec<Node<'a>>, Vec<Edge>) {
+        let radicals = self
+            .radicals
+            .iter()
+            .enumerate()
+            .map(|(i, (glyph, radical))| Node {
+                id: format!("r{}", i),
+                kind: "radical",
+                glyph,
+                strokes: radical.map(|radical| radical.strokes),
+                alternate: radical.and_then(|radical| match &radical.alternate {
+                    Alternate::Image(image) => Some(("image", image.as_str())),
+                    Alternate::Glyph(glyph) => Some(("glyph", glyph.as_str())),
+                    Alternate::None => None,
+                }),
+            });
+        match kind {
+            GraphKind::Bipartite => {
+                let kanji = self.kanji.iter().enumerate().map(|(i, glyph)| Node {
+                    id: format!("k{}", i),
+                    kind: "kanji",
+                    glyph,
+                    strokes: None,
+                    alternate: None,
+                });
+                let edges = self
+                    .edges
+                    .iter()
+                    .map(|(kanji, radical)| Edge {
+                        source: format!("r{}", radical),
+                        target: format!("k{}", kanji),
+                        weight: None,
+                    })
+                    .collect();
+                (radicals.chain(kanji).collect(), edges)
+            }
+            GraphKind::Cooccurrence => (radicals.collect(), self.cooccurrences()),
+        }
+    }
+
+    /// Links each pair of radicals that appear in the same kanji
+    fn cooccurrences(&self) -> Vec<Edge> {
+        let mut radicals_per_kanji = vec![vec![]; self.kanji.len()];
+        for &(kanji, radical) in self.edges.iter() {
+            radicals_per_kanji[kanji].push(radical);
+        }
+        let mut weights = BTreeMap::new();
+        for radicals in radicals_per_kanji.iter_mut() {
+            radicals.sort_unstable();
+            for (i, &left) in radicals.iter().enumerate() {
+                for &right in radicals[i + 1..].iter() {
+                    *weights.entry((left, right)).or_insert(0) += 1;
+                }
+            }
+        }
+        weights
+            .into_iter()
+            .map(|((left, right), weight)| Edge {
+                source: format!("r{}", left),
+                target: format!("r{}", right),
+                weight: Some(weight),
+            })
+            .collect()
+    }
+}
+
+fn dot_string(s: &str) -> String {
+    format!("\"{}\"", s.replace('\\', "\\\\").replace('"', "\\\""))
+}
+
+fn graphml_data(key: &str, value: &str) -> String {
+    format!("<data key=\"{}\">{}</data>", key, xml_escape(value))
+}
+
+fn xml_escape(s: &str) -> String {
+    s.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use kradical_parsing::test_support::radical;
+
+    const PAIRS: &[(&str, &str)] = &[
+        ("亜", "一"),
+        ("亜", "口"),
+        ("唖", "一"),
+        ("唖", "口"),
+        ("娃", "女"),
+    ];
+
+    #[test]
+    fn bipartite_dot() {
+        let one = radical("一", 1);
+        let details: HashMap<_, _> = vec![("一", &one)].into_iter().collect();
+        let graph = Graph::new(PAIRS.iter().copied(), &details, Some("亜"));
+        let mut dot = vec![];
+        graph.write_dot(GraphKind::Bipartite, &mut dot).unwrap();
+        assert_eq!(
+            String::from_utf8(dot).unwrap(),
+            [
+                "graph kradical {",
+                "\tnode [fontname=\"sans-serif\"];",
+                "\tr0 [label=\"一\", type=radical, shape=box, strokes=1];",
+                "\tr1 [label=\"口\", type=radical, shape=box];",
+                "\tk0 [label=\"亜\", type=kanji];",
+                "\tr0 -- k0;",
+                "\tr1 -- k0;",
+                "}",
+                "",
+            ]
+            .join("\n")
+        );
+    }
+
+    #[test]
+    fn cooccurrence_weights() {
+        let graph = Graph::new(PAIRS.iter().copied(), &HashMap::new(), None);
+        let mut graphml = vec![];
+        graph
+            .write_graphml(GraphKind::Cooccurrence, &mut graphml)
+            .unwrap();
+        let graphml = String::from_utf8(graphml).unwrap();
+        assert!(graphml
+            .contains("<edge source=\"r0\" target=\"r1\"><data key=\"weight\">2</data></edge>"));
+        assert_eq!(graphml.matches("<edge ").count(), 1);
+        assert!(!graphml.contains("kanji"));
+    }
+}
@@ -0,0 +1,77 @@
+use serde::Serialize;
+use std::io::Write;
+
+/// Layout of the generated JSON documents
+#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+pub enum JsonStyle {
+    /// Indented with one value per line
+    Pretty,
+
+    /// No insignificant whitespace
+    Compact,
+}
+
+pub fn to_json<T: Serialize + ?Sized>(value: &T, style: JsonStyle) -> serde_json::Result<String> {
+    match style {
+        JsonStyle::Pretty => serde_json::to_string_pretty(value),
+        JsonStyle::Compact => serde_json::to_string(value),
+    }
+}
+
+pub fn write_json<T: Serialize + ?Sized>(
+    value: &T,
+    style: JsonStyle,
+    out: &mut dyn Write,
+) -> serde_json::Result<()> {
+    match style {
+        JsonStyle::Pretty => serde_json::to_writer_pretty(out, value),
+        JsonStyle::Compact => serde_json::to_writer(out, value),
+    }
+}
+
+pub fn write_ndjson<T: Serialize>(
+    values: impl IntoIterator<Item = T>,
+    out: &mut dyn Write,
+) -> serde_json::Result<()> {
+    for value in values {
+        serde_json::to_writer(&mut *out, &value)?;
+        writeln!(out).map_err(serde_json::Error::io)?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn escapes_strings() {
+        let res = to_json(&["\"\\\n"], JsonStyle::Compact).unwrap();
+        assert_eq!(res, r#"["\"\\\n"]"#);
+    }
+
+    #[test]
+    fn pretty_is_indented() {
+        let res = to_json(&["一", "二"], JsonStyle::Pretty).unwrap();
+        assert_eq!(res, "[\n  \"一\",\n  \"二\"\n]");
+    }
+
+    #[test]
+    fn writes_like_to_json() {
+        let mut res = vec![];
+        write_json(&["一", "二"], JsonStyle::Pretty, &mut res).unwrap();
+        assert_eq!(
+            res,
+            to_json(&["一", "二"], JsonStyle::Pretty)
+                .unwrap()
+                .as_bytes()
+        );
+    }
+
+    #[test]
+    fn ndjson_has_one_value_per_line() {
+        let mut res = vec![];
+        write_ndjson(vec![["一"], ["二"]], &mut res).unwrap();
+        assert_eq!(res, "[\"一\"]\n[\"二\"]\n".as_bytes());
+    }
+}
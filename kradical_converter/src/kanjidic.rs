@@ -0,0 +1,126 @@
+use std::collections::HashMap;
+
+use crate::{error::ConvertError, files};
+use xml::reader::{EventReader, XmlEvent};
+
+/// The English meanings of each kanji in a KANJIDIC2 file
+pub type Meanings = HashMap<String, Vec<String>>;
+
+/// The newspaper frequency rank of each kanji in a KANJIDIC2 file,
+/// where 1 is the most frequent
+pub type Frequencies = HashMap<String, u16>;
+
+/// The stroke count of each kanji in a KANJIDIC2 file
+pub type StrokeCounts = HashMap<String, u8>;
+
+/// The parts of a KANJIDIC2 file used by the converter
+#[derive(Debug, Default)]
+pub struct Kanjidic {
+    pub meanings: Meanings,
+    pub frequencies: Frequencies,
+    pub stroke_counts: StrokeCounts,
+}
+
+/// Reads a KANJIDIC2 XML file, or stdin if the path is `-`
+pub fn parse(path: &str) -> Result<Kanjidic, ConvertError> {
+    parse_bytes(&files::read(path)?).map_err(|source| ConvertError::Kanjidic {
+        path: path.to_string(),
+        source,
+    })
+}
+
+fn parse_bytes(bytes: &[u8]) -> Result<Kanjidic, xml::reader::Error> {
+    let mut kanjidic = Kanjidic::default();
+    let mut literal = String::new();
+    let mut kanji_meanings = vec![];
+    let mut frequency = None;
+    let mut stroke_count = None;
+    // The element whose text is being read, if it is one of interest
+    let mut reading: Option<&str> = None;
+    let mut text = String::new();
+    for event in EventReader::new(bytes) {
+        match event? {
+            XmlEvent::StartElement {
+                name, attributes, ..
+            } => {
+                text.clear();
+                reading = match name.local_name.as_str() {
+                    "literal" => Some("literal"),
+                    "freq" => Some("freq"),
+                    "stroke_count" => Some("stroke_count"),
+                    // Meanings in other languages carry an m_lang attribute
+                    "meaning" if attributes.is_empty() => Some("meaning"),
+                    _ => None,
+                }
+            }
+            XmlEvent::Characters(characters) if reading.is_some() => text.push_str(&characters),
+            XmlEvent::EndElement { name } => {
+                match (name.local_name.as_str(), reading) {
+                    ("literal", Some("literal")) => literal = text.clone(),
+                    ("meaning", Some("meaning")) => kanji_meanings.push(text.clone()),
+                    ("freq", Some("freq")) => frequency = text.trim().parse().ok(),
+                    // Any later counts are common miscounts
+                    ("stroke_count", Some("stroke_count")) if stroke_count.is_none() => {
+                        stroke_count = text.trim().parse().ok()
+                    }
+                    ("character", _) => {
+                        if let Some(frequency) = frequency.take() {
+                            kanjidic.frequencies.insert(literal.clone(), frequency);
+                        }
+                        if let Some(stroke_count) = stroke_count.take() {
+                            kanjidic.stroke_counts.insert(literal.clone(), stroke_count);
+                        }
+                        kanjidic.meanings.insert(
+                            std::mem::take(&mut literal),
+                            std::mem::take(&mut kanji_meanings),
+                        );
+                    }
+                    _ => {}
+                }
+                reading = None;
+            }
+            _ => {}
+        }
+    }
+    Ok(kanjidic)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reads_meanings_frequencies_and_strokes() {
+        let xml = r#"<?xml version="1.0" encoding="UTF-8"?>
+<kanjidic2>
+<character>
+<literal>亜</literal>
+<reading_meaning>
+<rmgroup>
+<reading r_type="ja_on">ア</reading>
+<meaning>Asia</meaning>
+<meaning>rank next</meaning>
+<meaning m_lang="fr">Asie</meaning>
+</rmgroup>
+</reading_meaning>
+<misc>
+<stroke_count>7</stroke_count>
+<stroke_count>8</stroke_count>
+<freq>1509</freq>
+</misc>
+</character>
+<character>
+<literal>唖</literal>
+</character>
+</kanjidic2>"#;
+        let kanjidic = parse_bytes(xml.as_bytes()).unwrap();
+        assert_eq!(
+            kanjidic.meanings.get("亜"),
+            Some(&vec!["Asia".to_string(), "rank next".to_string()])
+        );
+        assert_eq!(kanjidic.meanings.get("唖"), Some(&vec![]));
+        assert_eq!(kanjidic.frequencies.get("亜"), Some(&1509));
+        assert_eq!(kanjidic.frequencies.get("唖"), None);
+        assert_eq!(kanjidic.stroke_counts.get("亜"), Some(&7));
+    }
+}
@@ -1,76 +1,310 @@
-use crate::opts::OutputFormat;
-use kradical_parsing::krad::{self, Decomposition, KradError};
-
-pub fn parse(inputs: &[String], format: OutputFormat) -> Result<String, KradError> {
-    let parsed: Result<Vec<_>, _> = inputs.iter().map(krad::parse_file).collect();
-    let parsed: Vec<_> = parsed?
-        .into_iter()
-        .flat_map(|file| file.into_iter())
-        .collect();
-    Ok(formatter(format)(&parsed))
+use crate::{
+    codegen::{c_string, python_tuple, quoted, write_lines},
+    error::ConvertError,
+    files,
+    graph::Graph,
+    json::{self, JsonStyle},
+    opts::{Layout, Merge},
+    order,
+    table::{self, Delimiter},
+};
+use kradical_parsing::{
+    krad::{self, Decomposition},
+    radk::Radical,
+};
+use serde::Serialize;
+use std::{
+    collections::HashMap,
+    io::{self, Write},
+};
+
+/// Parses the kradfiles, merging the radicals of repeated kanji
+pub fn parse(inputs: &[String]) -> Result<Vec<Decomposition>, ConvertError> {
+    parse_merged(inputs, Merge::Union)
 }
 
-fn formatter(format: OutputFormat) -> fn(&[Decomposition]) -> String {
-    match format {
-        OutputFormat::Unicode => to_unicode,
-        OutputFormat::Rust => to_rust,
-        OutputFormat::Json => to_json,
-    }
+/// Merges kradfiles that were parsed one at a time, as `parse` would
+pub fn merge(parsed: Vec<Decomposition>) -> Vec<Decomposition> {
+    order::merge_decompositions(parsed, Merge::Union)
 }
 
-fn to_json(decompositions: &[Decomposition]) -> String {
-    let mut lines = vec![];
-    lines.push("[".to_owned());
-    for (i, decomposition) in decompositions.iter().enumerate() {
-        lines.push("\t{".to_owned());
-        lines.push(format!("\t\t\"kanji\": \"{}\",", decomposition.kanji));
-        lines.push("\t\t\"radicals\": [".to_owned());
-        for (i, radical) in decomposition.radicals.iter().enumerate() {
-            if i == decomposition.radicals.len() - 1 {
-                lines.push(format!("\t\t\t\"{}\"", radical));
-            } else {
-                lines.push(format!("\t\t\t\"{}\",", radical));
+/// Parses the kradfiles, merging repeated kanji by the given policy
+pub fn parse_merged(inputs: &[String], merge: Merge) -> Result<Vec<Decomposition>, ConvertError> {
+    let mut parsed = vec![];
+    for input in inputs {
+        parsed.extend(krad::parse_bytes(&files::read(input)?).map_err(|source| {
+            ConvertError::Krad {
+                path: input.clone(),
+                source,
             }
-        }
-        lines.push("\t\t]".to_owned());
-        if i == decompositions.len() - 1 {
-            lines.push("\t}".to_owned());
-        } else {
-            lines.push("\t},".to_owned());
-        }
+        })?);
     }
-    lines.push("]".to_owned());
-    lines.join("\n")
+    Ok(order::merge_decompositions(parsed, merge))
 }
 
-fn to_unicode(decompositions: &[Decomposition]) -> String {
-    let lines: Vec<String> = decompositions
+/// The JSON representation of a decomposition
+#[derive(Serialize)]
+struct Record<'a> {
+    kanji: &'a str,
+    radicals: &'a [String],
+}
+
+fn to_records(decompositions: &[Decomposition]) -> Vec<Record<'_>> {
+    decompositions
         .iter()
-        .map(|decomposition| {
-            let radicals = decomposition.radicals.join(" ");
-            format!("{} : {}", decomposition.kanji, &radicals)
+        .map(|decomposition| Record {
+            kanji: &decomposition.kanji,
+            radicals: &decomposition.radicals,
         })
-        .collect();
-    lines.join("\n")
+        .collect()
+}
+
+pub fn write_json(
+    decompositions: &[Decomposition],
+    style: JsonStyle,
+    out: &mut dyn Write,
+) -> serde_json::Result<()> {
+    json::write_json(&to_records(decompositions), style, out)
+}
+
+pub fn write_ndjson(
+    decompositions: &[Decomposition],
+    out: &mut dyn Write,
+) -> serde_json::Result<()> {
+    json::write_ndjson(to_records(decompositions), out)
+}
+
+pub fn write_table(
+    decompositions: &[Decomposition],
+    layout: Layout,
+    delimiter: Delimiter,
+    out: &mut dyn Write,
+) -> Result<(), csv::Error> {
+    match layout {
+        Layout::Wide => table::write_table(
+            &["kanji", "radicals"],
+            decompositions.iter().map(|decomposition| {
+                vec![
+                    decomposition.kanji.clone(),
+                    decomposition.radicals.join(" "),
+                ]
+            }),
+            delimiter,
+            out,
+        ),
+        Layout::Long => table::write_table(
+            &["kanji", "radical"],
+            decompositions.iter().flat_map(|decomposition| {
+                decomposition
+                    .radicals
+                    .iter()
+                    .map(move |radical| vec![decomposition.kanji.clone(), radical.clone()])
+            }),
+            delimiter,
+            out,
+        ),
+    }
+}
+
+/// The graph of the decompositions, with radical details where known
+pub fn graph<'a>(
+    decompositions: &'a [Decomposition],
+    details: &HashMap<&str, &'a Radical>,
+    subset: Option<&str>,
+) -> Graph<'a> {
+    let pairs = decompositions.iter().flat_map(|decomposition| {
+        decomposition
+            .radicals
+            .iter()
+            .map(move |radical| (decomposition.kanji.as_str(), radical.as_str()))
+    });
+    Graph::new(pairs, details, subset)
+}
+
+pub fn write_unicode(decompositions: &[Decomposition], out: &mut dyn Write) -> io::Result<()> {
+    for (i, decomposition) in decompositions.iter().enumerate() {
+        if i > 0 {
+            writeln!(out)?;
+        }
+        let radicals = decomposition.radicals.join(" ");
+        write!(out, "{} : {}", decomposition.kanji, &radicals)?;
+    }
+    Ok(())
 }
 
-fn to_rust(decompositions: &[Decomposition]) -> String {
-    let mut lines = vec![
-        "use super::Decomposition;".to_string(),
-        "".to_string(),
-        "/// The list of radical decompositions from the `kradfile`".to_string(),
-        "pub const DECOMPOSITIONS: &[Decomposition] = &[".to_string(),
-    ];
+pub fn write_rust(decompositions: &[Decomposition], out: &mut dyn Write) -> io::Result<()> {
+    write_lines(
+        out,
+        &[
+            "use super::Decomposition;",
+            "",
+            "/// The list of radical decompositions from the `kradfile`",
+            "pub const DECOMPOSITIONS: &[Decomposition] = &[",
+        ],
+    )?;
     for decomposition in decompositions {
-        lines.push("\t Decomposition {".to_string());
-        lines.push(format!("\t\tkanji: \'{}\',", decomposition.kanji));
-        lines.push("\t\tradicals: &[".to_string());
+        writeln!(out, "\t Decomposition {{")?;
+        writeln!(out, "\t\tkanji: \'{}\',", decomposition.kanji)?;
+        writeln!(out, "\t\tradicals: &[")?;
         for radical in decomposition.radicals.iter() {
-            lines.push(format!("\t\t\t\'{}\',", radical));
+            writeln!(out, "\t\t\t\'{}\',", radical)?;
         }
-        lines.push("\t\t],".to_string());
-        lines.push("\t},".to_string());
+        writeln!(out, "\t\t],")?;
+        writeln!(out, "\t}},")?;
+    }
+    write!(out, "];")
+}
+
+pub fn write_typescript(decompositions: &[Decomposition], out: &mut dyn Write) -> io::Result<()> {
+    write_lines(
+        out,
+        &[
+            "/** The constituent radicals for a kanji */",
+            "export interface Decomposition {",
+            "\t/** The kanji */",
+            "\treadonly kanji: string;",
+            "\t/** The radicals contained in the kanji */",
+            "\treadonly radicals: readonly string[];",
+            "}",
+            "",
+            "/** The list of radical decompositions from the `kradfile` */",
+            "export const DECOMPOSITIONS: readonly Decomposition[] = [",
+        ],
+    )?;
+    for decomposition in decompositions {
+        let radicals: Vec<_> = decomposition.radicals.iter().map(|r| quoted(r)).collect();
+        writeln!(
+            out,
+            "\t{{ kanji: {}, radicals: [{}] }},",
+            quoted(&decomposition.kanji),
+            radicals.join(", ")
+        )?;
+    }
+    writeln!(out, "];")
+}
+
+pub fn write_python(decompositions: &[Decomposition], out: &mut dyn Write) -> io::Result<()> {
+    write_lines(
+        out,
+        &[
+            "\"\"\"Radical decompositions from the kradfile\"\"\"",
+            "",
+            "from typing import NamedTuple, Tuple",
+            "",
+            "",
+            "class Decomposition(NamedTuple):",
+            "    \"\"\"The constituent radicals for a kanji\"\"\"",
+            "",
+            "    kanji: str",
+            "    radicals: Tuple[str, ...]",
+            "",
+            "",
+            "DECOMPOSITIONS: Tuple[Decomposition, ...] = (",
+        ],
+    )?;
+    for decomposition in decompositions {
+        let radicals: Vec<_> = decomposition.radicals.iter().map(|r| quoted(r)).collect();
+        writeln!(
+            out,
+            "    Decomposition({}, {}),",
+            quoted(&decomposition.kanji),
+            python_tuple(&radicals)
+        )?;
+    }
+    writeln!(out, ")")
+}
+
+pub fn write_c(decompositions: &[Decomposition], out: &mut dyn Write) -> io::Result<()> {
+    write_lines(
+        out,
+        &[
+            "#ifndef KRADICAL_DECOMPOSITIONS_H",
+            "#define KRADICAL_DECOMPOSITIONS_H",
+            "",
+            "#include <stddef.h>",
+            "#include <string.h>",
+            "",
+            "/* The constituent radicals for a kanji as UTF-8 strings */",
+            "typedef struct {",
+            "\tconst char *kanji;",
+            "\tconst char *const *radicals;",
+            "\tsize_t radical_count;",
+            "} kradical_decomposition;",
+            "",
+        ],
+    )?;
+    // Empty initializers are invalid before C23, so empty lists get no
+    // array and point to NULL instead
+    for (i, decomposition) in decompositions.iter().enumerate() {
+        if decomposition.radicals.is_empty() {
+            continue;
+        }
+        let radicals: Vec<_> = decomposition.radicals.iter().map(|r| c_string(r)).collect();
+        writeln!(
+            out,
+            "static const char *const kradical_radicals_{}[] = {{{}}};",
+            i,
+            radicals.join(", ")
+        )?;
+    }
+    write_lines(
+        out,
+        &[
+            "",
+            "/* The list of radical decompositions from the kradfile */",
+            "static const kradical_decomposition KRADICAL_DECOMPOSITIONS[] = {",
+        ],
+    )?;
+    for (i, decomposition) in decompositions.iter().enumerate() {
+        let radicals = if decomposition.radicals.is_empty() {
+            "NULL".to_string()
+        } else {
+            format!("kradical_radicals_{}", i)
+        };
+        writeln!(
+            out,
+            "\t{{{}, {}, {}}},",
+            c_string(&decomposition.kanji),
+            radicals,
+            decomposition.radicals.len()
+        )?;
+    }
+    write_lines(
+        out,
+        &[
+            "};",
+            "",
+            "#define KRADICAL_DECOMPOSITION_COUNT \\",
+            "\t(sizeof(KRADICAL_DECOMPOSITIONS) / sizeof(KRADICAL_DECOMPOSITIONS[0]))",
+            "",
+            "/* Finds the decomposition of a UTF-8 kanji, or returns NULL */",
+            "static inline const kradical_decomposition *kradical_find_decomposition(const char *kanji) {",
+            "\tfor (size_t i = 0; i < KRADICAL_DECOMPOSITION_COUNT; i++) {",
+            "\t\tif (strcmp(KRADICAL_DECOMPOSITIONS[i].kanji, kanji) == 0) {",
+            "\t\t\treturn &KRADICAL_DECOMPOSITIONS[i];",
+            "\t\t}",
+            "\t}",
+            "\treturn NULL;",
+            "}",
+            "",
+            "#endif",
+        ],
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use kradical_parsing::test_support::decomposition;
+
+    #[test]
+    fn c_points_empty_lists_to_null() {
+        let decompositions = vec![decomposition("亜", &["一"]), decomposition("〇", &[])];
+        let mut out = vec![];
+        write_c(&decompositions, &mut out).unwrap();
+        let header = String::from_utf8(out).unwrap();
+        assert!(header.contains("kradical_radicals_0[] = {"));
+        assert!(!header.contains("kradical_radicals_1"));
+        assert!(header.contains("\", NULL, 0},\n};"));
     }
-    lines.push("];".to_string());
-    lines.join("\n")
 }
@@ -0,0 +1,33 @@
+//! Conversions of the EDRDG radical decomposition files into other formats.
+//! Each output format implements [`format::Formatter`], which writes
+//! decompositions, memberships or both to any [`std::io::Write`].
+
+mod binary;
+pub mod bmp;
+mod chart;
+mod codegen;
+pub mod combined;
+pub mod convert;
+pub mod coverage;
+pub mod diff;
+pub mod error;
+pub mod files;
+mod filter;
+pub mod format;
+mod glyphs;
+mod graph;
+pub mod json;
+pub mod kanjidic;
+pub mod krad;
+pub mod manifest;
+mod names;
+pub mod opts;
+mod order;
+mod overlay;
+pub mod query;
+pub mod radk;
+pub mod report;
+mod sqlite;
+pub mod stats;
+pub mod table;
+mod yomitan;
@@ -1,25 +1,124 @@
 use clap::Clap;
-use error::ConvertError;
-use std::{fs::OpenOptions, io::Write};
+use kradical_converter::{
+    convert, coverage, diff,
+    error::ConvertError,
+    files, kanjidic, krad, manifest,
+    opts::{Command, CoverageOpts, DiffOpts, DriftOpts, InputFormat, Opts, QueryOpts, StatsOpts},
+    query, radk, report, stats,
+};
 
-use crate::opts::{InputFormat, Opts};
+fn main() {
+    if let Err(err) = run(&Opts::parse()) {
+        eprintln!("error: {}", err);
+        std::process::exit(err.exit_code());
+    }
+}
+
+fn run(opts: &Opts) -> Result<(), ConvertError> {
+    match &opts.command {
+        Command::Radk(_)
+        | Command::Krad(_)
+        | Command::Combined(_)
+        | Command::KradToRadk(_)
+        | Command::RadkToKrad(_) => convert::write(&convert::run(&opts.command)?),
+        Command::Drift(opts) => drift(opts),
+        Command::Diff(opts) => diff(opts),
+        Command::Query(opts) => query(opts),
+        Command::Stats(opts) => stats(opts),
+        Command::Manifest(opts) => manifest::run(opts),
+        Command::Coverage(opts) => coverage(opts),
+    }
+}
 
-mod error;
-mod krad;
-mod opts;
-mod radk;
+fn drift(opts: &DriftOpts) -> Result<(), ConvertError> {
+    let decompositions = krad::parse(&opts.krad_inputs)?;
+    let memberships = radk::parse(&opts.radk_inputs)?;
+    let report = report::format(
+        &diff::Drift::new(&decompositions, &memberships),
+        opts.format,
+    )?;
+    files::write(&opts.output, report.as_bytes())?;
+    Ok(())
+}
+
+fn diff(opts: &DiffOpts) -> Result<(), ConvertError> {
+    let report = match opts.input_format {
+        InputFormat::Radk => {
+            let old = radk::parse(&opts.old)?;
+            let new = radk::parse(&opts.new)?;
+            report::format(&diff::RadkDiff::new(&old, &new), opts.format)?
+        }
+        InputFormat::Krad => {
+            let old = krad::parse(&opts.old)?;
+            let new = krad::parse(&opts.new)?;
+            report::format(&diff::KradDiff::new(&old, &new), opts.format)?
+        }
+    };
+    files::write(&opts.output, report.as_bytes())?;
+    Ok(())
+}
 
-fn main() -> Result<(), ConvertError> {
-    let opts = Opts::parse();
-    let text = match opts.input_format {
-        InputFormat::Radk => radk::parse(&opts.inputs, opts.output_format)?,
-        InputFormat::Krad => krad::parse(&opts.inputs, opts.output_format)?,
+fn query(opts: &QueryOpts) -> Result<(), ConvertError> {
+    let memberships = radk::parse(&opts.inputs)?;
+    let stroke_counts = match &opts.kanjidic {
+        Some(path) => Some(kanjidic::parse(path)?.stroke_counts),
+        None => None,
     };
-    OpenOptions::new()
-        .write(true)
-        .create(true)
-        .truncate(true)
-        .open(opts.output)
-        .and_then(|mut file| file.write_all(text.as_bytes()))?;
+    let result = query::QueryResult::new(
+        &memberships,
+        &opts.radicals,
+        &opts.exclude,
+        stroke_counts.as_ref(),
+    )?;
+    files::write(
+        &opts.output,
+        report::format(&result, opts.format)?.as_bytes(),
+    )?;
+    Ok(())
+}
+
+fn coverage(opts: &CoverageOpts) -> Result<(), ConvertError> {
+    let font = files::read(&opts.font)?;
+    let face = ttf_parser::Face::parse(&font, opts.index).map_err(|source| ConvertError::Font {
+        path: opts.font.clone(),
+        source,
+    })?;
+    let decompositions = match opts.krad_inputs.is_empty() {
+        true => coverage::static_decompositions(),
+        false => krad::parse(&opts.krad_inputs)?,
+    };
+    let memberships = match opts.radk_inputs.is_empty() {
+        true => coverage::static_memberships(),
+        false => radk::parse(&opts.radk_inputs)?,
+    };
+    let coverage = coverage::Coverage::new(&decompositions, &memberships, |c| {
+        face.glyph_index(c).is_some()
+    });
+    let report = report::format(&coverage, opts.format)?;
+    files::write(&opts.output, report.as_bytes())?;
+    Ok(())
+}
+
+fn stats(opts: &StatsOpts) -> Result<(), ConvertError> {
+    let mut files = vec![];
+    let mut decompositions = vec![];
+    for path in opts.krad_inputs.iter() {
+        let parsed = krad::parse(std::slice::from_ref(path))?;
+        files.push(stats::FileStats::from_decompositions(path, &parsed));
+        decompositions.extend(parsed);
+    }
+    let mut memberships = vec![];
+    for path in opts.radk_inputs.iter() {
+        let parsed = radk::parse(std::slice::from_ref(path))?;
+        files.push(stats::FileStats::from_memberships(path, &parsed));
+        memberships.extend(parsed);
+    }
+    let decompositions = krad::merge(decompositions);
+    let memberships = radk::merge(memberships)?;
+    let report = report::format(
+        &stats::Stats::new(files, &decompositions, &memberships, opts.top),
+        opts.format,
+    )?;
+    files::write(&opts.output, report.as_bytes())?;
     Ok(())
 }
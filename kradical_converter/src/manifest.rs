@@ -0,0 +1,268 @@
+use std::{
+    io::Write,
+    path::Path,
+    process::{Command as Process, Stdio},
+};
+
+use crate::{
+    convert,
+    error::ConvertError,
+    files,
+    opts::{Command, ConvertOpts, ManifestOpts, Opts, OutputFormat},
+};
+use clap::Clap;
+use serde::Deserialize;
+
+/// Every conversion needed to regenerate the committed outputs
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct Manifest {
+    /// Whether to pass generated Rust through rustfmt to match the
+    /// formatted sources
+    #[serde(default)]
+    rustfmt: bool,
+
+    #[serde(rename = "conversion")]
+    conversions: Vec<Conversion>,
+}
+
+/// A conversion command with the same meaning as on the command line
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields, rename_all = "kebab-case")]
+struct Conversion {
+    /// The command name, such as `krad` or `combined`
+    command: String,
+    inputs: Vec<String>,
+
+    #[serde(default)]
+    radk_inputs: Vec<String>,
+
+    /// Outputs prefixed with their formats, as in `json:krad.json`
+    outputs: Vec<String>,
+
+    /// Any other options, as in `["--compact"]`
+    #[serde(default)]
+    args: Vec<String>,
+}
+
+impl Conversion {
+    /// Builds the command line, with every path resolved against the
+    /// manifest's directory
+    fn opts(&self, directory: &Path) -> Result<Opts, ConvertError> {
+        let mut args = vec![env!("CARGO_PKG_NAME").to_string(), self.command.clone()];
+        args.push("--inputs".to_string());
+        args.extend(self.inputs.iter().cloned());
+        if !self.radk_inputs.is_empty() {
+            args.push("--radk-inputs".to_string());
+            args.extend(self.radk_inputs.iter().cloned());
+        }
+        args.push("--output".to_string());
+        args.extend(self.outputs.iter().cloned());
+        args.extend(self.args.iter().cloned());
+        let mut opts =
+            Opts::try_parse_from(args).map_err(|err| ConvertError::InvalidConversion {
+                command: self.command.clone(),
+                reason: err.to_string(),
+            })?;
+        match &mut opts.command {
+            Command::Radk(opts)
+            | Command::Krad(opts)
+            | Command::Combined(opts)
+            | Command::KradToRadk(opts)
+            | Command::RadkToKrad(opts) => resolve_paths(opts, directory),
+            _ => {}
+        }
+        Ok(opts)
+    }
+}
+
+/// Resolves the inputs, outputs and every option naming a file, since
+/// `--from` is compared against the resolved inputs
+fn resolve_paths(opts: &mut ConvertOpts, directory: &Path) {
+    let paths = opts
+        .inputs
+        .iter_mut()
+        .chain(opts.radk_inputs.iter_mut())
+        .chain(opts.output.iter_mut().map(|output| &mut output.path))
+        .chain(opts.from.iter_mut())
+        .chain(opts.kanjidic.iter_mut())
+        .chain(opts.overlay.iter_mut())
+        .chain(opts.overlay_report.iter_mut())
+        .chain(opts.kanji_list.iter_mut())
+        .chain(opts.bmp_report.iter_mut());
+    for path in paths {
+        *path = resolve(directory, path);
+    }
+}
+
+/// Regenerates the outputs listed in a manifest, or checks that
+/// the files on disk match them
+pub fn run(opts: &ManifestOpts) -> Result<(), ConvertError> {
+    let manifest: Manifest = toml::from_str(&String::from_utf8_lossy(&files::read(
+        &opts.manifest,
+    )?))
+    .map_err(|source| ConvertError::Manifest {
+        path: opts.manifest.clone(),
+        source,
+    })?;
+    // Paths in the manifest are relative to it
+    let directory = Path::new(&opts.manifest)
+        .parent()
+        .unwrap_or_else(|| Path::new(""));
+
+    let mut stale = vec![];
+    let mut count = 0;
+    for conversion in manifest.conversions.iter() {
+        let conversion_opts = conversion.opts(directory)?;
+        for output in convert::run(&conversion_opts.command)? {
+            let bytes = match output.format {
+                OutputFormat::Rust if manifest.rustfmt => rustfmt(&output.bytes)?,
+                _ => output.bytes,
+            };
+            count += 1;
+            if !opts.check {
+                files::write(output.path, &bytes)?;
+            } else if std::fs::read(output.path).ok().as_deref() != Some(bytes.as_slice()) {
+                stale.push(output.path.to_string());
+            }
+        }
+    }
+
+    if opts.check {
+        for path in stale.iter() {
+            eprintln!("{} is out of date", path);
+        }
+        if !stale.is_empty() {
+            return Err(ConvertError::StaleOutputs(stale.len()));
+        }
+        eprintln!("All {} outputs are up to date", count);
+    }
+    Ok(())
+}
+
+/// Joins a relative path onto the manifest's directory, leaving absolute
+/// paths and `-` for the standard streams as they are
+fn resolve(directory: &Path, path: &str) -> String {
+    if path == "-" || Path::new(path).is_absolute() {
+        path.to_string()
+    } else {
+        directory.join(path).to_string_lossy().into_owned()
+    }
+}
+
+fn rustfmt(source: &[u8]) -> Result<Vec<u8>, ConvertError> {
+    let spawn_error = |source| ConvertError::Spawn {
+        program: "rustfmt".to_string(),
+        source,
+    };
+    let mut child = Process::new("rustfmt")
+        .args(["--edition", "2018"])
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(spawn_error)?;
+    child
+        .stdin
+        .take()
+        .expect("stdin is piped")
+        .write_all(source)
+        .map_err(spawn_error)?;
+    let output = child.wait_with_output().map_err(spawn_error)?;
+    if output.status.success() {
+        Ok(output.stdout)
+    } else {
+        Err(ConvertError::Rustfmt(
+            String::from_utf8_lossy(&output.stderr).into_owned(),
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn builds_command_line() {
+        let manifest: Manifest = toml::from_str(
+            r#"
+            [[conversion]]
+            command = "krad-to-radk"
+            inputs = ["kradfile"]
+            radk-inputs = ["radkfile", "radkfile2"]
+            outputs = ["json:radk.json"]
+            args = ["--compact"]
+            "#,
+        )
+        .unwrap();
+        assert!(!manifest.rustfmt);
+        let opts = manifest.conversions[0].opts(Path::new("")).unwrap();
+        match opts.command {
+            Command::KradToRadk(opts) => {
+                assert_eq!(opts.inputs, vec!["kradfile"]);
+                assert_eq!(opts.radk_inputs, vec!["radkfile", "radkfile2"]);
+                assert_eq!(opts.output[0].format, Some(OutputFormat::Json));
+                assert!(opts.compact);
+            }
+            command => panic!("Unexpected command {:?}", command),
+        }
+    }
+
+    #[test]
+    fn rejects_unknown_commands() {
+        let conversion = Conversion {
+            command: "kradd".to_string(),
+            inputs: vec![],
+            radk_inputs: vec![],
+            outputs: vec![],
+            args: vec![],
+        };
+        assert!(matches!(
+            conversion.opts(Path::new("")),
+            Err(ConvertError::InvalidConversion { .. })
+        ));
+    }
+
+    #[test]
+    fn resolves_paths_against_the_manifest() {
+        let conversion = Conversion {
+            command: "krad".to_string(),
+            inputs: vec!["edrdg/kradfile".to_string(), "-".to_string()],
+            radk_inputs: vec![],
+            outputs: vec![
+                "json:out/krad.json".to_string(),
+                "/tmp/krad.txt".to_string(),
+            ],
+            args: [
+                "--from",
+                "edrdg/kradfile",
+                "--kanji-list",
+                "joyo.txt",
+                "--kanjidic",
+                "/data/kanjidic2.xml",
+                "--overlay",
+                "corrections.csv",
+                "--overlay-report",
+                "-",
+            ]
+            .iter()
+            .map(|arg| arg.to_string())
+            .collect(),
+        };
+        match conversion.opts(Path::new("assets")).unwrap().command {
+            Command::Krad(opts) => {
+                assert_eq!(opts.inputs, vec!["assets/edrdg/kradfile", "-"]);
+                assert_eq!(opts.output[0].format, Some(OutputFormat::Json));
+                assert_eq!(opts.output[0].path, "assets/out/krad.json");
+                assert_eq!(opts.output[1].format, None);
+                assert_eq!(opts.output[1].path, "/tmp/krad.txt");
+                assert_eq!(opts.from, vec!["assets/edrdg/kradfile"]);
+                assert_eq!(opts.kanji_list.as_deref(), Some("assets/joyo.txt"));
+                assert_eq!(opts.kanjidic.as_deref(), Some("/data/kanjidic2.xml"));
+                assert_eq!(opts.overlay.as_deref(), Some("assets/corrections.csv"));
+                assert_eq!(opts.overlay_report.as_deref(), Some("-"));
+            }
+            command => panic!("Unexpected command {:?}", command),
+        }
+    }
+}
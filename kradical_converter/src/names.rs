@@ -0,0 +1,265 @@
+/// English names for the radicals, taken from the Unicode names of the
+/// Kangxi radicals and CJK radicals supplement where those exist
+pub const RADICAL_NAMES: &[(&str, &str)] = &[
+    ("一", "one"),
+    ("ノ", "slash"),
+    ("｜", "stick"),
+    ("丶", "dot"),
+    ("亅", "hook"),
+    ("乙", "second"),
+    ("ハ", "ha"),
+    ("二", "two"),
+    ("十", "ten"),
+    ("丷", "horns"),
+    ("亠", "lid"),
+    ("冂", "down box"),
+    ("儿", "legs"),
+    ("勹", "wrap"),
+    ("⺅", "person"),
+    ("厶", "private"),
+    ("𠆢", "hat"),
+    ("冖", "cover"),
+    ("人", "man"),
+    ("又", "again"),
+    ("匕", "spoon"),
+    ("厂", "cliff"),
+    ("𠂉", "gun"),
+    ("卜", "divination"),
+    ("冫", "ice"),
+    ("凵", "open box"),
+    ("几", "table"),
+    ("刀", "knife"),
+    ("⺉", "knife two"),
+    ("匚", "right open box"),
+    ("力", "power"),
+    ("卩", "seal"),
+    ("マ", "ma"),
+    ("九", "nine"),
+    ("入", "enter"),
+    ("ユ", "yu"),
+    ("乃", "from"),
+    ("口", "mouth"),
+    ("土", "earth"),
+    ("⺾", "grass one"),
+    ("大", "big"),
+    ("小", "small"),
+    ("⺡", "water one"),
+    ("幺", "short thread"),
+    ("扌", "hand one"),
+    ("宀", "roof"),
+    ("女", "woman"),
+    ("山", "mountain"),
+    ("ヨ", "yo"),
+    ("尸", "corpse"),
+    ("廾", "two hands"),
+    ("夂", "go"),
+    ("⺖", "heart one"),
+    ("⻌", "simplified walk"),
+    ("广", "dotted cliff"),
+    ("寸", "inch"),
+    ("士", "scholar"),
+    ("囗", "enclosure"),
+    ("巾", "turban"),
+    ("彡", "bristle"),
+    ("工", "work"),
+    ("⺌", "small one"),
+    ("夕", "evening"),
+    ("弓", "bow"),
+    ("⻏", "city"),
+    ("子", "child"),
+    ("干", "dry"),
+    ("已", "already"),
+    ("⻖", "mound two"),
+    ("彳", "step"),
+    ("⺨", "dog side"),
+    ("巛", "river"),
+    ("弋", "shoot"),
+    ("屮", "sprout"),
+    ("廴", "long stride"),
+    ("尢", "lame"),
+    ("川", "three rivers"),
+    ("亡", "perish"),
+    ("也", "also"),
+    ("彑", "snout"),
+    ("及", "reach"),
+    ("久", "long time"),
+    ("日", "sun"),
+    ("木", "tree"),
+    ("月", "moon"),
+    ("⺣", "fire dots"),
+    ("王", "king"),
+    ("止", "stop"),
+    ("心", "heart"),
+    ("戈", "halberd"),
+    ("火", "fire"),
+    ("攵", "strike"),
+    ("爪", "claw"),
+    ("曰", "say"),
+    ("方", "square"),
+    ("水", "water"),
+    ("斤", "axe"),
+    ("比", "compare"),
+    ("欠", "lack"),
+    ("殳", "weapon"),
+    ("氏", "clan"),
+    ("牛", "cow"),
+    ("⺹", "old"),
+    ("勿", "must not"),
+    ("⺭", "spirit two"),
+    ("爿", "half tree trunk"),
+    ("戸", "door"),
+    ("犬", "dog"),
+    ("歹", "death"),
+    ("支", "branch"),
+    ("巴", "comma"),
+    ("手", "hand"),
+    ("元", "origin"),
+    ("父", "father"),
+    ("爻", "double x"),
+    ("文", "script"),
+    ("毋", "do not"),
+    ("片", "slice"),
+    ("斗", "dipper"),
+    ("五", "five"),
+    ("屯", "barracks"),
+    ("井", "well"),
+    ("无", "not"),
+    ("尤", "especially"),
+    ("毛", "fur"),
+    ("气", "steam"),
+    ("田", "field"),
+    ("目", "eye"),
+    ("立", "stand"),
+    ("禾", "grain"),
+    ("⺲", "net two"),
+    ("白", "white"),
+    ("石", "stone"),
+    ("皿", "dish"),
+    ("⽧", "sickness"),
+    ("⻂", "clothes side"),
+    ("矢", "arrow"),
+    ("示", "spirit"),
+    ("甘", "sweet"),
+    ("疋", "bolt of cloth"),
+    ("用", "use"),
+    ("矛", "spear"),
+    ("⽱", "track"),
+    ("冊", "volume"),
+    ("穴", "cave"),
+    ("生", "life"),
+    ("玄", "profound"),
+    ("世", "world"),
+    ("母", "mother"),
+    ("癶", "dotted tent"),
+    ("牙", "fang"),
+    ("皮", "skin"),
+    ("瓦", "tile"),
+    ("巨", "giant"),
+    ("虫", "insect"),
+    ("糸", "silk"),
+    ("竹", "bamboo"),
+    ("米", "rice"),
+    ("衣", "clothes"),
+    ("耳", "ear"),
+    ("臼", "mortar"),
+    ("羽", "feather"),
+    ("羊", "sheep"),
+    ("虍", "tiger"),
+    ("西", "west"),
+    ("艮", "stopping"),
+    ("自", "self"),
+    ("舟", "boat"),
+    ("而", "and"),
+    ("聿", "brush"),
+    ("缶", "jar"),
+    ("至", "arrive"),
+    ("行", "walk enclosure"),
+    ("舌", "tongue"),
+    ("耒", "plow"),
+    ("瓜", "melon"),
+    ("血", "blood"),
+    ("色", "color"),
+    ("肉", "meat"),
+    ("貝", "shell"),
+    ("言", "speech"),
+    ("車", "cart"),
+    ("足", "foot"),
+    ("豆", "bean"),
+    ("酉", "wine"),
+    ("豕", "pig"),
+    ("里", "village"),
+    ("辛", "bitter"),
+    ("見", "see"),
+    ("臣", "minister"),
+    ("角", "horn"),
+    ("走", "run"),
+    ("辰", "morning"),
+    ("舛", "oppose"),
+    ("谷", "valley"),
+    ("釆", "distinguish"),
+    ("豸", "badger"),
+    ("身", "body"),
+    ("赤", "red"),
+    ("麦", "barley"),
+    ("金", "gold"),
+    ("隹", "short tailed bird"),
+    ("門", "gate"),
+    ("雨", "rain"),
+    ("長", "long"),
+    ("非", "wrong"),
+    ("青", "blue"),
+    ("免", "excuse"),
+    ("隶", "slave"),
+    ("奄", "suddenly"),
+    ("岡", "ridge"),
+    ("斉", "uniform"),
+    ("頁", "leaf"),
+    ("革", "leather"),
+    ("音", "sound"),
+    ("食", "eat"),
+    ("風", "wind"),
+    ("品", "goods"),
+    ("韭", "leek"),
+    ("面", "face"),
+    ("首", "head"),
+    ("香", "fragrant"),
+    ("飛", "fly"),
+    ("馬", "horse"),
+    ("髟", "hair"),
+    ("骨", "bone"),
+    ("鬼", "ghost"),
+    ("韋", "tanned leather"),
+    ("高", "tall"),
+    ("鬲", "cauldron"),
+    ("竜", "dragon"),
+    ("鬥", "fight"),
+    ("鬯", "sacrificial wine"),
+    ("魚", "fish"),
+    ("鳥", "bird"),
+    ("鹿", "deer"),
+    ("黒", "black"),
+    ("黄", "yellow"),
+    ("麻", "hemp"),
+    ("啇", "root"),
+    ("亀", "turtle"),
+    ("鹵", "salt"),
+    ("歯", "tooth"),
+    ("無", "nothing"),
+    ("黍", "millet"),
+    ("黹", "embroidery"),
+    ("鼠", "rat"),
+    ("黽", "frog"),
+    ("鼓", "drum"),
+    ("鼎", "tripod"),
+    ("齊", "even"),
+    ("鼻", "nose"),
+    ("龠", "flute"),
+];
+
+/// Finds the radical with a name, ignoring case
+pub fn radical_for_name(name: &str) -> Option<&'static str> {
+    RADICAL_NAMES
+        .iter()
+        .find(|(_, candidate)| candidate.eq_ignore_ascii_case(name))
+        .map(|(radical, _)| *radical)
+}
@@ -1,19 +1,392 @@
-use clap::{AppSettings, Clap};
+use clap::{AppSettings, ArgEnum, Clap};
+use std::str::FromStr;
+
+use crate::{error::ConvertError, json::JsonStyle};
+use kradical_static::Repertoire;
 
 #[derive(Clap, Clone, PartialEq, Eq, Debug)]
-#[clap(setting = AppSettings::ColoredHelp)]
+#[clap(global_setting = AppSettings::ColoredHelp)]
 pub struct Opts {
+    #[clap(subcommand)]
+    pub command: Command,
+}
+
+#[derive(Clap, Clone, PartialEq, Eq, Debug)]
+pub enum Command {
+    /// Converts radkfiles
+    Radk(ConvertOpts),
+
+    /// Converts kradfiles
+    Krad(ConvertOpts),
+
+    /// Converts kradfiles joined with radkfiles
+    Combined(ConvertOpts),
+
+    /// Derives radkfile memberships from kradfiles,
+    /// taking radical stroke counts from --radk-inputs
+    KradToRadk(ConvertOpts),
+
+    /// Derives kradfile decompositions from radkfiles
+    RadkToKrad(ConvertOpts),
+
+    /// Compares kradfiles with radkfiles to find where they disagree
+    Drift(DriftOpts),
+
+    /// Compares two versions of the kradfiles or radkfiles
+    Diff(DiffOpts),
+
+    /// Finds the kanji containing a set of radicals
+    Query(QueryOpts),
+
+    /// Summarizes kradfiles and radkfiles for sanity checks
+    Stats(StatsOpts),
+
+    /// Regenerates every output listed in a TOML manifest
+    Manifest(ManifestOpts),
+
+    /// Lists the radicals and kanji that a font lacks glyphs for
+    Coverage(CoverageOpts),
+}
+
+#[derive(Clap, Clone, PartialEq, Eq, Debug)]
+pub struct ConvertOpts {
+    /// The format of any output not prefixed with its own
     #[clap(arg_enum)]
-    pub input_format: InputFormat,
+    pub output_format: Option<OutputFormat>,
+
+    /// Files to parse, or `-` for stdin
+    #[clap(short, long, required = true)]
+    pub inputs: Vec<String>,
+
+    /// The radkfiles to join with the kradfile inputs
+    /// when using the combined or krad-to-radk commands
+    #[clap(long)]
+    pub radk_inputs: Vec<String>,
+
+    /// Files to write, or `-` for stdout. Prefix a path with a format
+    /// to write several formats at once, as in `json:radk.json`.
+    #[clap(short, long, default_value = "-")]
+    pub output: Vec<Output>,
+
+    /// Write JSON without insignificant whitespace
+    #[clap(long, conflicts_with = "pretty")]
+    pub compact: bool,
+
+    /// Write indented JSON, which is the default
+    #[clap(long)]
+    pub pretty: bool,
+
+    /// Row layout for CSV and TSV
+    #[clap(long, arg_enum, default_value = "wide")]
+    pub layout: Layout,
+
+    /// URL of the alternate radical images in HTML and SVG charts,
+    /// with `{}` in place of the image name, as in `images/{}.png`.
+    /// Radicals are shown as glyphs when omitted.
+    #[clap(long)]
+    pub image_url: Option<String>,
+
+    /// A KANJIDIC2 XML file supplying English meanings for the anki
+    /// format of the combined command, and ranks for the frequency order
+    #[clap(long)]
+    pub kanjidic: Option<String>,
+
+    /// The order of kanji and radicals. Radicals default to stroke order
+    /// and decompositions to file order.
+    #[clap(long, arg_enum)]
+    pub order: Option<Order>,
+
+    /// How to merge a kanji or radical listed more than once in the inputs
+    #[clap(long, arg_enum, default_value = "union")]
+    pub merge: Merge,
+
+    /// A CSV file of corrections to apply before writing, or TSV if
+    /// its name ends in `.tsv`
+    #[clap(long)]
+    pub overlay: Option<String>,
+
+    /// File to write the report of applied corrections to.
+    /// The report goes to stderr when omitted.
+    #[clap(long, requires = "overlay")]
+    pub overlay_report: Option<String>,
+
+    #[clap(long, arg_enum, default_value = "text")]
+    pub overlay_report_format: ReportFormat,
+
+    /// Keep only the kanji listed in these files from --inputs
+    #[clap(long)]
+    pub from: Vec<String>,
 
+    /// Keep only the kanji in this file. Whitespace and lines
+    /// starting with `#` are ignored.
+    #[clap(long)]
+    pub kanji_list: Option<String>,
+
+    /// Keep only the kanji up to this JIS X 0208 level
+    #[clap(long, possible_values = &["1", "2"])]
+    pub jis_level: Option<u8>,
+
+    /// Keep only the kanji in these code point ranges, as in `4E00-9FFF`
+    #[clap(long)]
+    pub codepoints: Vec<CodepointRange>,
+
+    /// Write every radical in one repertoire rather than the mix
+    /// produced by the parser
+    #[clap(long, arg_enum)]
+    pub radical_glyphs: Option<RadicalGlyphs>,
+
+    /// Replace characters outside the Basic Multilingual Plane with
+    /// their fallbacks, failing if any has none
+    #[clap(long)]
+    pub bmp: bool,
+
+    /// File to write the report of characters outside the BMP to.
+    /// The report goes to stderr when omitted.
+    #[clap(long, requires = "bmp")]
+    pub bmp_report: Option<String>,
+
+    #[clap(long, arg_enum, default_value = "text")]
+    pub bmp_report_format: ReportFormat,
+
+    /// The graph written by the dot and graphml formats
+    #[clap(long, arg_enum, default_value = "bipartite")]
+    pub graph: GraphKind,
+
+    /// Restrict the dot and graphml formats to these kanji, as in `亜唖娃`
+    #[clap(long)]
+    pub subset: Option<String>,
+}
+
+impl ConvertOpts {
+    pub fn outputs(&self) -> Result<Vec<(OutputFormat, &str)>, ConvertError> {
+        self.output
+            .iter()
+            .map(|output| {
+                output
+                    .format
+                    .or(self.output_format)
+                    .map(|format| (format, output.path.as_str()))
+                    .ok_or_else(|| ConvertError::MissingFormat(output.path.clone()))
+            })
+            .collect()
+    }
+
+    pub fn format_options(&self) -> FormatOptions<'_> {
+        FormatOptions {
+            json_style: if self.compact {
+                JsonStyle::Compact
+            } else {
+                JsonStyle::Pretty
+            },
+            layout: self.layout,
+            image_url: self.image_url.as_deref(),
+            graph: self.graph,
+            subset: self.subset.as_deref(),
+        }
+    }
+}
+
+#[derive(Clap, Clone, PartialEq, Eq, Debug)]
+pub struct DiffOpts {
     #[clap(arg_enum)]
-    pub output_format: OutputFormat,
+    pub input_format: InputFormat,
+
+    /// Files from the older release
+    #[clap(long, required = true)]
+    pub old: Vec<String>,
+
+    /// Files from the newer release
+    #[clap(long, required = true)]
+    pub new: Vec<String>,
 
+    /// File to write the report to, or `-` for stdout
+    #[clap(short, long, default_value = "-")]
+    pub output: String,
+
+    #[clap(short, long, arg_enum, default_value = "text")]
+    pub format: ReportFormat,
+}
+
+#[derive(Clap, Clone, PartialEq, Eq, Debug)]
+pub struct DriftOpts {
+    /// The kradfiles to compare
+    #[clap(long, required = true)]
+    pub krad_inputs: Vec<String>,
+
+    /// The radkfiles to compare
+    #[clap(long, required = true)]
+    pub radk_inputs: Vec<String>,
+
+    /// File to write the report to, or `-` for stdout
+    #[clap(short, long, default_value = "-")]
+    pub output: String,
+
+    #[clap(short, long, arg_enum, default_value = "text")]
+    pub format: ReportFormat,
+}
+
+#[derive(Clap, Clone, PartialEq, Eq, Debug)]
+pub struct QueryOpts {
+    /// Radicals the kanji must contain, given as glyphs or English names
+    #[clap(required = true)]
+    pub radicals: Vec<String>,
+
+    /// Radicals the kanji must not contain
+    #[clap(short = 'x', long)]
+    pub exclude: Vec<String>,
+
+    /// The radkfiles to search
     #[clap(short, long, required = true)]
     pub inputs: Vec<String>,
 
-    #[clap(short, long)]
+    /// A KANJIDIC2 XML file supplying the stroke counts of kanji
+    #[clap(long)]
+    pub kanjidic: Option<String>,
+
+    /// File to write the results to, or `-` for stdout
+    #[clap(short, long, default_value = "-")]
     pub output: String,
+
+    #[clap(short, long, arg_enum, default_value = "text")]
+    pub format: ReportFormat,
+}
+
+#[derive(Clap, Clone, PartialEq, Eq, Debug)]
+pub struct StatsOpts {
+    /// The kradfiles to summarize
+    #[clap(long, required = true)]
+    pub krad_inputs: Vec<String>,
+
+    /// The radkfiles to summarize
+    #[clap(long, required = true)]
+    pub radk_inputs: Vec<String>,
+
+    /// The number of radicals in the most and least common lists
+    #[clap(long, default_value = "10")]
+    pub top: usize,
+
+    /// File to write the report to, or `-` for stdout
+    #[clap(short, long, default_value = "-")]
+    pub output: String,
+
+    #[clap(short, long, arg_enum, default_value = "text")]
+    pub format: ReportFormat,
+}
+
+#[derive(Clap, Clone, PartialEq, Eq, Debug)]
+pub struct CoverageOpts {
+    /// The kradfiles whose kanji to check, in place of the kanji
+    /// shipped in kradical_static
+    #[clap(long)]
+    pub krad_inputs: Vec<String>,
+
+    /// The radkfiles whose radicals to check, in place of the radicals
+    /// shipped in kradical_static
+    #[clap(long)]
+    pub radk_inputs: Vec<String>,
+
+    /// The TTF, OTF or TTC font file to check
+    #[clap(long)]
+    pub font: String,
+
+    /// The index of the font to check within a TTC collection
+    #[clap(long, default_value = "0")]
+    pub index: u32,
+
+    /// File to write the report to, or `-` for stdout
+    #[clap(short, long, default_value = "-")]
+    pub output: String,
+
+    #[clap(short, long, arg_enum, default_value = "text")]
+    pub format: ReportFormat,
+}
+
+#[derive(Clap, Clone, PartialEq, Eq, Debug)]
+pub struct ManifestOpts {
+    /// The manifest listing the conversions to run
+    #[clap(default_value = "kradical.toml")]
+    pub manifest: String,
+
+    /// Compare the outputs with the files on disk instead of writing them,
+    /// failing if any differ
+    #[clap(long)]
+    pub check: bool,
+}
+
+/// A destination for converted data
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct Output {
+    /// The format to write, if different from the default
+    pub format: Option<OutputFormat>,
+
+    /// The file to write to
+    pub path: String,
+}
+
+impl FromStr for Output {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let prefixed = s.split_once(':').and_then(|(format, path)| {
+            OutputFormat::from_str(format, true)
+                .ok()
+                .map(|format| (format, path))
+        });
+        Ok(match prefixed {
+            Some((format, path)) => Self {
+                format: Some(format),
+                path: path.to_string(),
+            },
+            None => Self {
+                format: None,
+                path: s.to_string(),
+            },
+        })
+    }
+}
+
+/// An inclusive range of code points
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct CodepointRange {
+    pub start: u32,
+    pub end: u32,
+}
+
+impl CodepointRange {
+    pub fn contains(&self, c: char) -> bool {
+        (self.start..=self.end).contains(&(c as u32))
+    }
+}
+
+impl FromStr for CodepointRange {
+    type Err = String;
+
+    /// Parses hexadecimal code points, optionally prefixed with `U+`,
+    /// as either a single code point or a range like `4E00-9FFF`
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let parse = |s: &str| {
+            let digits = s.trim_start_matches("U+").trim_start_matches("u+");
+            u32::from_str_radix(digits, 16).map_err(|_| format!("Invalid code point {}", s))
+        };
+        let (start, end) = match s.split_once('-') {
+            Some((start, end)) => (parse(start)?, parse(end)?),
+            None => (parse(s)?, parse(s)?),
+        };
+        if start > end {
+            return Err(format!("Range {} ends before it starts", s));
+        }
+        Ok(Self { start, end })
+    }
+}
+
+/// Settings shared by the output formatters
+#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+pub struct FormatOptions<'a> {
+    pub json_style: JsonStyle,
+    pub layout: Layout,
+    pub image_url: Option<&'a str>,
+    pub graph: GraphKind,
+    pub subset: Option<&'a str>,
 }
 
 #[derive(PartialEq, Eq, Clone, Copy, Clap, Debug)]
@@ -26,5 +399,106 @@ pub enum InputFormat {
 pub enum OutputFormat {
     Unicode,
     Rust,
+    Typescript,
+    Python,
+    C,
+    Json,
+    Ndjson,
+    Csv,
+    Tsv,
+    Sqlite,
+    Binary,
+    Html,
+    Svg,
+    Anki,
+    Yomitan,
+    Dot,
+    Graphml,
+}
+
+/// Arrangement of rows in tabular outputs
+#[derive(PartialEq, Eq, Clone, Copy, Clap, Debug)]
+pub enum Layout {
+    /// One row per kanji or radical with the related characters in one cell
+    Wide,
+
+    /// One row per kanji and radical pair
+    Long,
+}
+
+/// Orderings of converted kanji and radicals
+#[derive(PartialEq, Eq, Clone, Copy, Clap, Debug)]
+pub enum Order {
+    /// The order in which they first appear in the inputs
+    File,
+
+    /// Kanji and radicals by code point
+    Codepoint,
+
+    /// Radicals by stroke count, then by number of kanji, then by code point.
+    /// Kanji by code point.
+    Strokes,
+
+    /// Radicals in the order the radkfiles list them. Kanji by code point.
+    Index,
+
+    /// Kanji by KANJIDIC2 frequency rank, with unranked kanji last.
+    /// Radicals in file order.
+    Frequency,
+}
+
+/// Policies for merging a kanji or radical listed more than once
+#[derive(PartialEq, Eq, Clone, Copy, Clap, Debug)]
+pub enum Merge {
+    /// Keep every related character from each listing
+    Union,
+
+    /// Keep the first listing
+    First,
+
+    /// Keep the last listing
+    Last,
+}
+
+/// Repertoires for writing radicals
+#[derive(PartialEq, Eq, Clone, Copy, Clap, Debug)]
+pub enum RadicalGlyphs {
+    /// The characters of the EDRDG files, before radical replacements
+    Original,
+
+    /// CJK Unified Ideographs
+    Unified,
+
+    /// Kangxi Radicals and CJK Radicals Supplement where available
+    Radicals,
+}
+
+impl From<RadicalGlyphs> for Repertoire {
+    fn from(glyphs: RadicalGlyphs) -> Self {
+        match glyphs {
+            RadicalGlyphs::Original => Repertoire::Original,
+            RadicalGlyphs::Unified => Repertoire::Unified,
+            RadicalGlyphs::Radicals => Repertoire::Radicals,
+        }
+    }
+}
+
+/// Nodes and edges of graph outputs
+#[derive(PartialEq, Eq, Clone, Copy, Clap, Debug)]
+pub enum GraphKind {
+    /// Radicals and kanji linked by membership
+    Bipartite,
+
+    /// Radicals linked when they appear in the same kanji
+    Cooccurrence,
+}
+
+/// Presentation of reports
+#[derive(PartialEq, Eq, Clone, Copy, Clap, Debug)]
+pub enum ReportFormat {
+    /// Human-readable text
+    Text,
+
+    /// Indented JSON
     Json,
 }
@@ -0,0 +1,306 @@
+use std::{
+    cmp::Ordering,
+    collections::{HashMap, HashSet},
+};
+
+use crate::{
+    error::ConvertError,
+    kanjidic::Frequencies,
+    opts::{Merge, Order},
+};
+use kradical_parsing::{
+    krad::Decomposition,
+    radk::{Membership, Radical},
+};
+
+/// Merges decompositions of the same kanji, keeping the position of its
+/// first appearance
+pub fn merge_decompositions(parsed: Vec<Decomposition>, merge: Merge) -> Vec<Decomposition> {
+    let mut merged: Vec<Decomposition> = vec![];
+    let mut positions: HashMap<String, usize> = HashMap::new();
+    for decomposition in parsed {
+        match positions.get(&decomposition.kanji) {
+            Some(&i) => merge_into(&mut merged[i].radicals, decomposition.radicals, merge),
+            None => {
+                positions.insert(decomposition.kanji.clone(), merged.len());
+                merged.push(decomposition);
+            }
+        }
+    }
+    merged
+}
+
+/// Merges memberships of the same radical, keeping the position of its
+/// first appearance. Kanji are listed once per radical.
+pub fn merge_memberships(parsed: Vec<Membership>, merge: Merge) -> Vec<Membership> {
+    let mut merged: Vec<Membership> = vec![];
+    let mut positions: HashMap<Radical, usize> = HashMap::new();
+    for Membership { radical, kanji } in parsed {
+        let i = *positions.entry(radical.clone()).or_insert_with(|| {
+            merged.push(Membership {
+                radical,
+                kanji: vec![],
+            });
+            merged.len() - 1
+        });
+        let existing = &mut merged[i].kanji;
+        if existing.is_empty() || merge == Merge::Last {
+            existing.clear();
+            merge_into(existing, kanji, Merge::Union);
+        } else {
+            merge_into(existing, kanji, merge);
+        }
+    }
+    merged
+}
+
+fn merge_into(existing: &mut Vec<String>, repeated: Vec<String>, merge: Merge) {
+    match merge {
+        Merge::Union => {
+            let mut seen: HashSet<_> = existing.iter().cloned().collect();
+            existing.extend(
+                repeated
+                    .into_iter()
+                    .filter(|item| seen.insert(item.clone())),
+            );
+        }
+        Merge::First => {}
+        Merge::Last => *existing = unique(repeated),
+    }
+}
+
+/// Removes repeated items, keeping the first of each
+fn unique(items: Vec<String>) -> Vec<String> {
+    let mut seen = HashSet::new();
+    items
+        .into_iter()
+        .filter(|item| seen.insert(item.clone()))
+        .collect()
+}
+
+/// Sorts memberships and the kanji within them. Sorts are stable, so ties
+/// keep the order of the files.
+pub fn sort_memberships(
+    memberships: &mut [Membership],
+    order: Order,
+    frequencies: Option<&Frequencies>,
+) -> Result<(), ConvertError> {
+    let kanji_order = KanjiOrder::new(order, frequencies)?;
+    for membership in memberships.iter_mut() {
+        kanji_order.sort(&mut membership.kanji);
+    }
+    match order {
+        Order::Codepoint => memberships.sort_by(|l, r| l.radical.glyph.cmp(&r.radical.glyph)),
+        Order::Strokes => memberships.sort_by(|l, r| {
+            l.radical
+                .strokes
+                .cmp(&r.radical.strokes)
+                .then(r.kanji.len().cmp(&l.kanji.len()))
+                .then(l.radical.glyph.cmp(&r.radical.glyph))
+        }),
+        Order::File | Order::Index | Order::Frequency => {}
+    }
+    Ok(())
+}
+
+/// Sorts decompositions and the radicals within them
+///
+/// # Arguments
+///
+/// * `radicals` - The radkfile memberships, in file order, which supply
+///   radical stroke counts and positions for the strokes and index orders
+/// * `frequencies` - Kanji frequency ranks for the frequency order
+pub fn sort_decompositions(
+    decompositions: &mut [Decomposition],
+    order: Order,
+    radicals: Option<&[Membership]>,
+    frequencies: Option<&Frequencies>,
+) -> Result<(), ConvertError> {
+    match order {
+        Order::Strokes | Order::Index => {
+            let mut ranks = HashMap::new();
+            for (i, membership) in radicals
+                .ok_or(ConvertError::MissingRadkInputs)?
+                .iter()
+                .enumerate()
+            {
+                let rank = match order {
+                    Order::Strokes => membership.radical.strokes as usize,
+                    _ => i,
+                };
+                ranks
+                    .entry(membership.radical.glyph.as_str())
+                    .or_insert(rank);
+            }
+            // Radicals missing from the radkfiles go last
+            let rank = |glyph: &String| ranks.get(glyph.as_str()).copied();
+            for decomposition in decompositions.iter_mut() {
+                decomposition
+                    .radicals
+                    .sort_by(|l, r| missing_last(&rank(l), &rank(r)).then(l.cmp(r)));
+            }
+        }
+        Order::Codepoint => {
+            for decomposition in decompositions.iter_mut() {
+                decomposition.radicals.sort();
+            }
+        }
+        Order::File | Order::Frequency => {}
+    }
+
+    let kanji_order = KanjiOrder::new(order, frequencies)?;
+    decompositions.sort_by(|l, r| kanji_order.cmp(&l.kanji, &r.kanji));
+    Ok(())
+}
+
+/// How kanji are ordered, in lists of kanji and of decompositions
+enum KanjiOrder<'a> {
+    File,
+    Codepoint,
+    Frequency(&'a Frequencies),
+}
+
+impl<'a> KanjiOrder<'a> {
+    fn new(order: Order, frequencies: Option<&'a Frequencies>) -> Result<Self, ConvertError> {
+        Ok(match order {
+            Order::File => Self::File,
+            Order::Codepoint | Order::Strokes | Order::Index => Self::Codepoint,
+            Order::Frequency => Self::Frequency(frequencies.ok_or(ConvertError::MissingKanjidic)?),
+        })
+    }
+
+    fn cmp(&self, l: &str, r: &str) -> Ordering {
+        match self {
+            Self::File => Ordering::Equal,
+            Self::Codepoint => l.cmp(r),
+            // Kanji without a frequency rank go last
+            Self::Frequency(frequencies) => {
+                missing_last(&frequencies.get(l), &frequencies.get(r)).then(l.cmp(r))
+            }
+        }
+    }
+
+    fn sort(&self, kanji: &mut [String]) {
+        kanji.sort_by(|l, r| self.cmp(l, r));
+    }
+}
+
+/// Compares optional values, placing `None` after everything else
+fn missing_last<T: Ord>(l: &Option<T>, r: &Option<T>) -> Ordering {
+    match (l, r) {
+        (Some(l), Some(r)) => l.cmp(r),
+        (Some(_), None) => Ordering::Less,
+        (None, Some(_)) => Ordering::Greater,
+        (None, None) => Ordering::Equal,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use kradical_parsing::test_support::{decomposition, membership};
+
+    #[test]
+    fn merges_by_policy() {
+        let parsed = || {
+            vec![
+                decomposition("唖", &["口", "一"]),
+                decomposition("亜", &["一"]),
+                decomposition("唖", &["｜", "口"]),
+            ]
+        };
+        assert_eq!(
+            merge_decompositions(parsed(), Merge::Union),
+            vec![
+                decomposition("唖", &["口", "一", "｜"]),
+                decomposition("亜", &["一"])
+            ]
+        );
+        assert_eq!(
+            merge_decompositions(parsed(), Merge::First)[0],
+            decomposition("唖", &["口", "一"])
+        );
+        assert_eq!(
+            merge_decompositions(parsed(), Merge::Last)[0],
+            decomposition("唖", &["｜", "口"])
+        );
+
+        let merged = merge_memberships(
+            vec![
+                membership("口", 3, &["唖", "亜"]),
+                membership("口", 3, &["叶", "唖"]),
+            ],
+            Merge::Union,
+        );
+        assert_eq!(merged, vec![membership("口", 3, &["唖", "亜", "叶"])]);
+    }
+
+    #[test]
+    fn sorts_memberships() {
+        let memberships = || {
+            vec![
+                membership("口", 3, &["唖", "叶"]),
+                membership("一", 1, &["唖"]),
+                membership("｜", 1, &["唖", "亜"]),
+            ]
+        };
+        let glyphs = |memberships: &[Membership]| -> Vec<String> {
+            memberships
+                .iter()
+                .map(|membership| membership.radical.glyph.clone())
+                .collect()
+        };
+
+        let mut sorted = memberships();
+        sort_memberships(&mut sorted, Order::Strokes, None).unwrap();
+        assert_eq!(glyphs(&sorted), vec!["｜", "一", "口"]);
+        assert_eq!(sorted[0].kanji, vec!["亜", "唖"]);
+
+        let mut sorted = memberships();
+        sort_memberships(&mut sorted, Order::Codepoint, None).unwrap();
+        assert_eq!(glyphs(&sorted), vec!["一", "口", "｜"]);
+
+        let mut sorted = memberships();
+        sort_memberships(&mut sorted, Order::File, None).unwrap();
+        assert_eq!(sorted, memberships());
+
+        let frequencies: Frequencies = vec![("叶".to_string(), 1)].into_iter().collect();
+        let mut sorted = memberships();
+        sort_memberships(&mut sorted, Order::Frequency, Some(&frequencies)).unwrap();
+        assert_eq!(sorted[0].kanji, vec!["叶", "唖"]);
+        assert!(matches!(
+            sort_memberships(&mut sorted, Order::Frequency, None),
+            Err(ConvertError::MissingKanjidic)
+        ));
+    }
+
+    #[test]
+    fn sorts_decompositions() {
+        let decompositions = || {
+            vec![
+                decomposition("唖", &["口", "邑", "一"]),
+                decomposition("亜", &["一", "口"]),
+            ]
+        };
+        let radicals = vec![membership("口", 3, &[]), membership("一", 1, &[])];
+
+        let mut sorted = decompositions();
+        sort_decompositions(&mut sorted, Order::Strokes, Some(&radicals), None).unwrap();
+        assert_eq!(
+            sorted,
+            vec![
+                decomposition("亜", &["一", "口"]),
+                decomposition("唖", &["一", "口", "邑"]),
+            ]
+        );
+
+        let mut sorted = decompositions();
+        sort_decompositions(&mut sorted, Order::Index, Some(&radicals), None).unwrap();
+        assert_eq!(sorted[1], decomposition("唖", &["口", "一", "邑"]));
+
+        assert!(matches!(
+            sort_decompositions(&mut sorted, Order::Index, None, None),
+            Err(ConvertError::MissingRadkInputs)
+        ));
+    }
+}
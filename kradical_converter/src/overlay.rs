@@ -0,0 +1,420 @@
+use std::fmt::{self, Display, Formatter};
+
+use crate::{error::ConvertError, files, report::heading};
+use csv::ReaderBuilder;
+use kradical_parsing::{
+    krad::Decomposition,
+    radk::{Alternate, Membership},
+};
+use serde::{Deserialize, Serialize};
+
+/// Local corrections to the EDRDG data, read from a CSV file with
+/// `action`, `target` and `value` columns
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Overlay {
+    pub corrections: Vec<Correction>,
+}
+
+/// A row of the overlay file
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Correction {
+    /// The line of the overlay file, counting the header
+    pub line: u64,
+    pub action: Action,
+
+    /// The kanji or radical to correct
+    pub target: String,
+    pub value: String,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Action {
+    /// Adds the space-separated radicals to a kanji
+    Add,
+
+    /// Removes the space-separated radicals from a kanji
+    Remove,
+
+    /// Replaces the radicals of a kanji
+    Replace,
+
+    /// Sets the stroke count of a radical
+    Strokes,
+
+    /// Sets the alternate of a radical to `image:<name>`, `glyph:<glyph>` or `none`
+    Alternate,
+}
+
+#[derive(Deserialize)]
+struct Row {
+    action: Action,
+    target: String,
+    value: String,
+}
+
+/// Which corrections changed the data
+#[derive(Debug, Default, Clone, PartialEq, Eq, Serialize)]
+pub struct OverlayReport {
+    pub applied: Vec<Correction>,
+
+    /// Corrections whose target was found but already matched
+    pub no_ops: Vec<Correction>,
+
+    /// Corrections whose target is in none of the inputs
+    pub unknown: Vec<Correction>,
+}
+
+/// Reads an overlay file, which is tab-separated if its name ends in `.tsv`.
+/// Lines starting with `#` are comments.
+pub fn parse(path: &str) -> Result<Overlay, ConvertError> {
+    let delimiter = if path.ends_with(".tsv") { b'\t' } else { b',' };
+    parse_bytes(&files::read(path)?, delimiter, path)
+}
+
+fn parse_bytes(bytes: &[u8], delimiter: u8, path: &str) -> Result<Overlay, ConvertError> {
+    let csv_error = |source| ConvertError::Overlay {
+        path: path.to_string(),
+        source,
+    };
+    let mut reader = ReaderBuilder::new()
+        .delimiter(delimiter)
+        .flexible(true)
+        .trim(csv::Trim::All)
+        .from_reader(bytes);
+    let headers = reader.headers().map_err(csv_error)?.clone();
+    let mut corrections = vec![];
+    for record in reader.records() {
+        let record = record.map_err(csv_error)?;
+        // Skipped here rather than by the reader so line numbers count comments
+        if record.get(0).is_some_and(|field| field.starts_with('#')) {
+            continue;
+        }
+        let line = record.position().map_or(0, |position| position.line());
+        let row: Row = record.deserialize(Some(&headers)).map_err(csv_error)?;
+        let correction = Correction {
+            line,
+            action: row.action,
+            target: row.target,
+            value: row.value,
+        };
+        match correction.action {
+            Action::Strokes if correction.value.parse::<u8>().is_err() => {
+                return Err(invalid(path, &correction, "stroke counts must be a number"))
+            }
+            Action::Alternate if parse_alternate(&correction.value).is_none() => {
+                return Err(invalid(
+                    path,
+                    &correction,
+                    "alternates must be image:<name>, glyph:<glyph> or none",
+                ))
+            }
+            _ => {}
+        }
+        corrections.push(correction);
+    }
+    Ok(Overlay { corrections })
+}
+
+fn invalid(path: &str, correction: &Correction, reason: &str) -> ConvertError {
+    ConvertError::InvalidCorrection {
+        path: path.to_string(),
+        line: correction.line,
+        reason: reason.to_string(),
+    }
+}
+
+fn parse_alternate(value: &str) -> Option<Alternate> {
+    match value.split_once(':') {
+        Some(("image", name)) if !name.is_empty() => Some(Alternate::Image(name.to_string())),
+        Some(("glyph", glyph)) if !glyph.is_empty() => Some(Alternate::Glyph(glyph.to_string())),
+        None if value == "none" => Some(Alternate::None),
+        _ => None,
+    }
+}
+
+/// The result of applying a correction
+enum Outcome {
+    Applied,
+    NoOp,
+    Unknown,
+}
+
+impl Overlay {
+    /// Applies the corrections to whichever file families were parsed,
+    /// keeping decompositions and memberships in agreement
+    pub fn apply(
+        &self,
+        mut decompositions: Option<&mut Vec<Decomposition>>,
+        mut memberships: Option<&mut Vec<Membership>>,
+    ) -> OverlayReport {
+        let mut report = OverlayReport::default();
+        for correction in self.corrections.iter() {
+            let outcome = match correction.action {
+                Action::Strokes | Action::Alternate => correct_radical(
+                    correction,
+                    decompositions.as_deref(),
+                    memberships.as_deref_mut(),
+                ),
+                Action::Add | Action::Remove | Action::Replace => correct_kanji(
+                    correction,
+                    decompositions.as_deref_mut(),
+                    memberships.as_deref_mut(),
+                ),
+            };
+            match outcome {
+                Outcome::Applied => report.applied.push(correction.clone()),
+                Outcome::NoOp => report.no_ops.push(correction.clone()),
+                Outcome::Unknown => report.unknown.push(correction.clone()),
+            }
+        }
+        report
+    }
+}
+
+fn correct_kanji(
+    correction: &Correction,
+    decompositions: Option<&mut Vec<Decomposition>>,
+    memberships: Option<&mut Vec<Membership>>,
+) -> Outcome {
+    let kanji = &correction.target;
+    let radicals: Vec<String> = correction
+        .value
+        .split_whitespace()
+        .map(String::from)
+        .collect();
+    let corrected = |current: &[String]| -> Vec<String> {
+        match correction.action {
+            Action::Add => {
+                let mut corrected = current.to_vec();
+                for radical in radicals.iter() {
+                    if !corrected.contains(radical) {
+                        corrected.push(radical.clone());
+                    }
+                }
+                corrected
+            }
+            Action::Remove => current
+                .iter()
+                .filter(|radical| !radicals.contains(radical))
+                .cloned()
+                .collect(),
+            _ => radicals.clone(),
+        }
+    };
+
+    // Without stroke counts, a radical cannot be added to the memberships,
+    // so neither family is changed
+    let adds_unknown_radical = correction.action != Action::Remove
+        && memberships.as_deref().is_some_and(|memberships| {
+            radicals.iter().any(|radical| {
+                !memberships
+                    .iter()
+                    .any(|membership| &membership.radical.glyph == radical)
+            })
+        });
+    if adds_unknown_radical {
+        return Outcome::Unknown;
+    }
+
+    let mut known = false;
+    let mut changed = false;
+    if let Some(decompositions) = decompositions {
+        for decomposition in decompositions
+            .iter_mut()
+            .filter(|decomposition| &decomposition.kanji == kanji)
+        {
+            known = true;
+            let radicals = corrected(&decomposition.radicals);
+            if radicals != decomposition.radicals {
+                decomposition.radicals = radicals;
+                changed = true;
+            }
+        }
+    }
+    if let Some(memberships) = memberships {
+        let current: Vec<String> = memberships
+            .iter()
+            .filter(|membership| membership.kanji.contains(kanji))
+            .map(|membership| membership.radical.glyph.clone())
+            .collect();
+        known |= !current.is_empty();
+        if known {
+            let radicals = corrected(&current);
+            for membership in memberships.iter_mut() {
+                let member = membership.kanji.contains(kanji);
+                let wanted = radicals.contains(&membership.radical.glyph);
+                if member && !wanted {
+                    membership.kanji.retain(|member| member != kanji);
+                    changed = true;
+                } else if !member && wanted {
+                    membership.kanji.push(kanji.clone());
+                    changed = true;
+                }
+            }
+        }
+    }
+
+    match (known, changed) {
+        (false, _) => Outcome::Unknown,
+        (true, true) => Outcome::Applied,
+        (true, false) => Outcome::NoOp,
+    }
+}
+
+fn correct_radical(
+    correction: &Correction,
+    decompositions: Option<&Vec<Decomposition>>,
+    memberships: Option<&mut Vec<Membership>>,
+) -> Outcome {
+    let glyph = &correction.target;
+    let mut known = decompositions.is_some_and(|decompositions| {
+        decompositions
+            .iter()
+            .any(|decomposition| decomposition.radicals.contains(glyph))
+    });
+    let mut changed = false;
+    if let Some(memberships) = memberships {
+        for membership in memberships
+            .iter_mut()
+            .filter(|membership| &membership.radical.glyph == glyph)
+        {
+            known = true;
+            let radical = &mut membership.radical;
+            match correction.action {
+                Action::Strokes => {
+                    // Validated while parsing
+                    let strokes = correction.value.parse().unwrap_or(radical.strokes);
+                    changed |= radical.strokes != strokes;
+                    radical.strokes = strokes;
+                }
+                _ => {
+                    let alternate = parse_alternate(&correction.value)
+                        .unwrap_or_else(|| radical.alternate.clone());
+                    changed |= radical.alternate != alternate;
+                    radical.alternate = alternate;
+                }
+            }
+        }
+    }
+
+    match (known, changed) {
+        (false, _) => Outcome::Unknown,
+        (true, true) => Outcome::Applied,
+        (true, false) => Outcome::NoOp,
+    }
+}
+
+impl Display for Correction {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let action = match self.action {
+            Action::Add => "add",
+            Action::Remove => "remove",
+            Action::Replace => "replace",
+            Action::Strokes => "strokes",
+            Action::Alternate => "alternate",
+        };
+        write!(
+            f,
+            "line {}: {} {} {}",
+            self.line, action, self.target, self.value
+        )
+    }
+}
+
+impl Display for OverlayReport {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let sections = [
+            ("Applied", &self.applied),
+            ("No effect", &self.no_ops),
+            ("Unknown kanji or radicals", &self.unknown),
+        ];
+        for (title, corrections) in sections.iter() {
+            heading(f, title, corrections.len())?;
+            for correction in corrections.iter() {
+                writeln!(f, "  {}", correction)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use kradical_parsing::test_support::{decomposition, membership};
+
+    const OVERLAY: &str = "\
+action,target,value
+# Corrections from the review
+add,亜,二
+remove,亜,口
+replace,唖,口 一
+strokes,口,4
+alternate,口,none
+add,鬱,木
+";
+
+    #[test]
+    fn applies_to_both_families() {
+        let overlay = parse_bytes(OVERLAY.as_bytes(), b',', "overlay.csv").unwrap();
+        let mut decompositions = vec![
+            decomposition("亜", &["一", "口"]),
+            decomposition("唖", &["口", "一"]),
+        ];
+        let mut memberships = vec![
+            membership("一", 1, &["亜", "唖"]),
+            membership("口", 3, &["亜", "唖"]),
+            membership("二", 2, &[]),
+        ];
+        let report = overlay.apply(Some(&mut decompositions), Some(&mut memberships));
+
+        assert_eq!(
+            decompositions,
+            vec![
+                decomposition("亜", &["一", "二"]),
+                decomposition("唖", &["口", "一"]),
+            ]
+        );
+        assert_eq!(
+            memberships,
+            vec![
+                membership("一", 1, &["亜", "唖"]),
+                membership("口", 4, &["唖"]),
+                membership("二", 2, &["亜"]),
+            ]
+        );
+        let lines = |corrections: &[Correction]| -> Vec<u64> {
+            corrections
+                .iter()
+                .map(|correction| correction.line)
+                .collect()
+        };
+        assert_eq!(lines(&report.applied), vec![3, 4, 6]);
+        assert_eq!(lines(&report.no_ops), vec![5, 7]);
+        assert_eq!(lines(&report.unknown), vec![8]);
+    }
+
+    #[test]
+    fn skips_radicals_missing_from_memberships() {
+        let overlay = "action,target,value\nadd,亜,二\n";
+        let overlay = parse_bytes(overlay.as_bytes(), b',', "overlay.csv").unwrap();
+        let mut decompositions = vec![decomposition("亜", &["一"])];
+        let mut memberships = vec![membership("一", 1, &["亜"])];
+        let report = overlay.apply(Some(&mut decompositions), Some(&mut memberships));
+
+        assert_eq!(decompositions, vec![decomposition("亜", &["一"])]);
+        assert_eq!(memberships, vec![membership("一", 1, &["亜"])]);
+        assert!(report.applied.is_empty());
+        assert_eq!(report.unknown.len(), 1);
+    }
+
+    #[test]
+    fn rejects_invalid_values() {
+        let overlay = "action,target,value\nstrokes,口,three\n";
+        assert!(matches!(
+            parse_bytes(overlay.as_bytes(), b',', "overlay.csv"),
+            Err(ConvertError::InvalidCorrection { line: 2, .. })
+        ));
+    }
+}
@@ -0,0 +1,177 @@
+use std::{
+    collections::{BTreeSet, HashMap},
+    fmt::{self, Display, Formatter},
+};
+
+use crate::{error::ConvertError, kanjidic::StrokeCounts, names};
+use kradical_parsing::radk::{Alternate, Membership};
+use serde::Serialize;
+
+/// The kanji containing a set of radicals
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct QueryResult {
+    /// The radicals the kanji must contain
+    pub radicals: Vec<String>,
+
+    /// The radicals the kanji must not contain
+    pub excluded: Vec<String>,
+
+    /// The matching kanji in order of stroke count
+    pub candidates: Vec<Candidate>,
+}
+
+/// A kanji matching a query
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Candidate {
+    pub kanji: String,
+
+    /// The stroke count from KANJIDIC2, or else the total strokes of the
+    /// kanji's radicals as an estimate
+    pub strokes: u16,
+
+    /// Whether the stroke count is an estimate
+    pub estimated: bool,
+}
+
+impl QueryResult {
+    /// Finds the kanji containing every radical and none of the excluded
+    /// ones, ordered by their stroke counts where known
+    pub fn new(
+        memberships: &[Membership],
+        radicals: &[String],
+        excluded: &[String],
+        stroke_counts: Option<&StrokeCounts>,
+    ) -> Result<Self, ConvertError> {
+        let included = resolve_all(memberships, radicals)?;
+        let excluded = resolve_all(memberships, excluded)?;
+
+        let mut candidates: Option<BTreeSet<&str>> = None;
+        for membership in included.iter() {
+            let kanji = membership.kanji.iter().map(String::as_str).collect();
+            candidates = Some(match candidates {
+                Some(candidates) => candidates.intersection(&kanji).copied().collect(),
+                None => kanji,
+            });
+        }
+        let mut candidates = candidates.unwrap_or_default();
+        for membership in excluded.iter() {
+            for kanji in membership.kanji.iter() {
+                candidates.remove(kanji.as_str());
+            }
+        }
+
+        let strokes = estimated_strokes(memberships);
+        let mut candidates: Vec<_> = candidates
+            .into_iter()
+            .map(
+                |kanji| match stroke_counts.and_then(|stroke_counts| stroke_counts.get(kanji)) {
+                    Some(&strokes) => Candidate {
+                        kanji: kanji.to_string(),
+                        strokes: strokes as u16,
+                        estimated: false,
+                    },
+                    None => Candidate {
+                        kanji: kanji.to_string(),
+                        strokes: strokes.get(kanji).copied().unwrap_or_default(),
+                        estimated: true,
+                    },
+                },
+            )
+            .collect();
+        candidates.sort_by(|l, r| l.strokes.cmp(&r.strokes).then(l.kanji.cmp(&r.kanji)));
+
+        Ok(Self {
+            radicals: glyphs(&included),
+            excluded: glyphs(&excluded),
+            candidates,
+        })
+    }
+}
+
+fn glyphs(memberships: &[&Membership]) -> Vec<String> {
+    memberships
+        .iter()
+        .map(|membership| membership.radical.glyph.clone())
+        .collect()
+}
+
+fn resolve_all<'a>(
+    memberships: &'a [Membership],
+    radicals: &[String],
+) -> Result<Vec<&'a Membership>, ConvertError> {
+    radicals
+        .iter()
+        .map(|radical| {
+            resolve(memberships, radical)
+                .ok_or_else(|| ConvertError::UnknownRadical(radical.clone()))
+        })
+        .collect()
+}
+
+/// Finds a radical by its glyph, its alternate glyph, or its name
+fn resolve<'a>(memberships: &'a [Membership], radical: &str) -> Option<&'a Membership> {
+    let glyph = names::radical_for_name(radical).unwrap_or(radical);
+    memberships
+        .iter()
+        .find(|membership| membership.radical.glyph == glyph)
+        .or_else(|| {
+            memberships.iter().find(|membership| {
+                matches!(&membership.radical.alternate, Alternate::Glyph(alternate) if alternate == glyph)
+            })
+        })
+}
+
+fn estimated_strokes(memberships: &[Membership]) -> HashMap<&str, u16> {
+    let mut strokes = HashMap::new();
+    for membership in memberships {
+        for kanji in membership.kanji.iter() {
+            *strokes.entry(kanji.as_str()).or_default() += membership.radical.strokes as u16;
+        }
+    }
+    strokes
+}
+
+impl Display for QueryResult {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        if self.candidates.is_empty() {
+            return writeln!(f, "No matching kanji");
+        }
+        for group in self.candidates.chunk_by(|l, r| l.strokes == r.strokes) {
+            let kanji: Vec<_> = group
+                .iter()
+                .map(|candidate| candidate.kanji.as_str())
+                .collect();
+            writeln!(f, "{} : {}", group[0].strokes, kanji.join(" "))?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use kradical_parsing::test_support::membership;
+
+    #[test]
+    fn prefers_kanjidic_strokes() {
+        let memberships = [
+            membership("口", 3, &["品", "唖"]),
+            membership("一", 1, &["唖"]),
+        ];
+        let stroke_counts: StrokeCounts = vec![("唖".to_string(), 10)].into_iter().collect();
+        let result =
+            QueryResult::new(&memberships, &["口".to_string()], &[], Some(&stroke_counts)).unwrap();
+        let candidates: Vec<_> = result
+            .candidates
+            .iter()
+            .map(|candidate| {
+                (
+                    candidate.kanji.as_str(),
+                    candidate.strokes,
+                    candidate.estimated,
+                )
+            })
+            .collect();
+        assert_eq!(candidates, [("品", 3, true), ("唖", 10, false)]);
+    }
+}
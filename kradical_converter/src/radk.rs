@@ -1,120 +1,428 @@
-use std::collections::{HashMap, HashSet};
-
-use crate::opts::OutputFormat;
-use kradical_parsing::radk::{self, Membership, Radical, RadkError};
-
-pub fn parse(inputs: &[String], format: OutputFormat) -> Result<String, RadkError> {
-    let parsed: Result<Vec<_>, _> = inputs.iter().map(radk::parse_file).collect();
-    let parsed: Vec<_> = parsed?
-        .into_iter()
-        .flat_map(|file| file.into_iter())
-        .collect();
-    let parsed = consolidate(parsed);
-    Ok(formatter(format)(&parsed))
-}
-
-fn formatter(format: OutputFormat) -> fn(&[Membership]) -> String {
-    match format {
-        OutputFormat::Unicode => to_unicode,
-        OutputFormat::Rust => to_rust,
-        OutputFormat::Json => to_json,
-    }
+use std::{
+    collections::HashMap,
+    io::{self, Write},
+};
+
+use crate::{
+    codegen::{c_string, python_tuple, quoted, write_lines},
+    error::ConvertError,
+    files,
+    graph::Graph,
+    json::{self, JsonStyle},
+    opts::{Layout, Merge, Order},
+    order,
+    table::{self, Delimiter},
+};
+use kradical_parsing::radk::{self, Alternate, Membership, Radical};
+use serde::Serialize;
+
+/// Parses the radkfiles, merging the kanji of repeated radicals,
+/// and sorts the radicals by stroke count
+pub fn parse(inputs: &[String]) -> Result<Vec<Membership>, ConvertError> {
+    let mut memberships = parse_merged(inputs, Merge::Union)?;
+    order::sort_memberships(&mut memberships, Order::Strokes, None)?;
+    Ok(memberships)
 }
 
-fn to_json(expansions: &[Membership]) -> String {
-    let mut lines = vec!["[".to_owned()];
-    for (i, expansion) in expansions.iter().enumerate() {
-        lines.push("\t{".to_owned());
-        lines.push(format!("\t\t\"radical\": \"{}\",", expansion.radical.glyph));
-        lines.push(format!("\t\t\"stroke\": {},", expansion.radical.strokes));
-        lines.push("\t\t\"kanji\": [".to_owned());
-        for (i, kanji) in expansion.kanji.iter().enumerate() {
-            if i == expansion.kanji.len() - 1 {
-                lines.push(format!("\t\t\t\"{}\"", kanji));
-            } else {
-                lines.push(format!("\t\t\t\"{}\",", kanji));
+/// Merges radkfiles that were parsed one at a time, as `parse` would
+pub fn merge(parsed: Vec<Membership>) -> Result<Vec<Membership>, ConvertError> {
+    let mut memberships = order::merge_memberships(parsed, Merge::Union);
+    order::sort_memberships(&mut memberships, Order::Strokes, None)?;
+    Ok(memberships)
+}
+
+/// Parses the radkfiles, merging repeated radicals by the given policy
+/// but leaving them in file order
+pub fn parse_merged(inputs: &[String], merge: Merge) -> Result<Vec<Membership>, ConvertError> {
+    let mut parsed = vec![];
+    for input in inputs {
+        parsed.extend(radk::parse_bytes(&files::read(input)?).map_err(|source| {
+            ConvertError::Radk {
+                path: input.clone(),
+                source,
             }
-        }
-        lines.push("\t\t]".to_owned());
-        if i == expansions.len() - 1 {
-            lines.push("\t}".to_owned());
-        } else {
-            lines.push("\t},".to_owned());
+        })?);
+    }
+    Ok(order::merge_memberships(parsed, merge))
+}
+
+/// The JSON representation of a membership
+#[derive(Serialize)]
+pub struct Record<'a> {
+    radical: &'a str,
+    stroke: u8,
+    alternate: Option<AlternateRecord<'a>>,
+    kanji: &'a [String],
+}
+
+/// The JSON representation of an alternate radical representation
+#[derive(Serialize)]
+#[serde(tag = "type", content = "value", rename_all = "lowercase")]
+pub enum AlternateRecord<'a> {
+    Image(&'a str),
+    Glyph(&'a str),
+}
+
+impl<'a> AlternateRecord<'a> {
+    pub fn new(alternate: &'a Alternate) -> Option<Self> {
+        match alternate {
+            Alternate::Image(image) => Some(Self::Image(image)),
+            Alternate::Glyph(glyph) => Some(Self::Glyph(glyph)),
+            Alternate::None => None,
         }
     }
-    lines.push("]".to_owned());
-    lines.join("\n")
 }
 
-fn to_unicode(expansions: &[Membership]) -> String {
-    let lines: Vec<_> = expansions
+pub fn to_records(expansions: &[Membership]) -> Vec<Record<'_>> {
+    expansions
         .iter()
-        .map(|expansion| {
-            let kanji = expansion.kanji.join(" ");
-            let radical = &expansion.radical;
-            format!("{} {} : {}", radical.glyph, radical.strokes, kanji)
+        .map(|expansion| Record {
+            radical: &expansion.radical.glyph,
+            stroke: expansion.radical.strokes,
+            alternate: AlternateRecord::new(&expansion.radical.alternate),
+            kanji: &expansion.kanji,
         })
-        .collect();
-    lines.join("\n")
+        .collect()
+}
+
+pub fn write_json(
+    memberships: &[Membership],
+    style: JsonStyle,
+    out: &mut dyn Write,
+) -> serde_json::Result<()> {
+    json::write_json(&to_records(memberships), style, out)
+}
+
+pub fn write_ndjson(memberships: &[Membership], out: &mut dyn Write) -> serde_json::Result<()> {
+    json::write_ndjson(to_records(memberships), out)
+}
+
+pub fn write_table(
+    expansions: &[Membership],
+    layout: Layout,
+    delimiter: Delimiter,
+    out: &mut dyn Write,
+) -> Result<(), csv::Error> {
+    let header = ["radical", "strokes", "alternate", "kanji"];
+    let columns = |radical: &Radical, kanji: String| {
+        vec![
+            radical.glyph.clone(),
+            radical.strokes.to_string(),
+            alternate_text(&radical.alternate)
+                .unwrap_or_default()
+                .to_string(),
+            kanji,
+        ]
+    };
+    match layout {
+        Layout::Wide => table::write_table(
+            &header,
+            expansions
+                .iter()
+                .map(|expansion| columns(&expansion.radical, expansion.kanji.join(" "))),
+            delimiter,
+            out,
+        ),
+        Layout::Long => table::write_table(
+            &header,
+            expansions.iter().flat_map(|expansion| {
+                expansion
+                    .kanji
+                    .iter()
+                    .map(move |kanji| columns(&expansion.radical, kanji.clone()))
+            }),
+            delimiter,
+            out,
+        ),
+    }
+}
+
+/// Radical details by glyph
+pub fn details(memberships: &[Membership]) -> HashMap<&str, &Radical> {
+    memberships
+        .iter()
+        .map(|membership| (membership.radical.glyph.as_str(), &membership.radical))
+        .collect()
+}
+
+/// The graph of the kanji containing each radical
+pub fn graph<'a>(memberships: &'a [Membership], subset: Option<&str>) -> Graph<'a> {
+    let pairs = memberships.iter().flat_map(|membership| {
+        membership
+            .kanji
+            .iter()
+            .map(move |kanji| (kanji.as_str(), membership.radical.glyph.as_str()))
+    });
+    Graph::new(pairs, &details(memberships), subset)
+}
+
+/// The glyph or image name of an alternate representation
+pub fn alternate_text(alternate: &Alternate) -> Option<&str> {
+    match alternate {
+        Alternate::Image(alternate) | Alternate::Glyph(alternate) => Some(alternate),
+        Alternate::None => None,
+    }
 }
 
-fn to_rust(expansions: &[Membership]) -> String {
-    let mut lines = vec![
-        "use super::Membership;".to_string(),
-        "".to_string(),
-        "/// For each radical, a list of which kanji contain it from the `radkfile`".to_string(),
-        "pub const MEMBERSHIPS: &[Membership] = &[".to_string(),
-    ];
+pub fn write_unicode(expansions: &[Membership], out: &mut dyn Write) -> io::Result<()> {
+    for (i, expansion) in expansions.iter().enumerate() {
+        if i > 0 {
+            writeln!(out)?;
+        }
+        let kanji = expansion.kanji.join(" ");
+        let radical = &expansion.radical;
+        match alternate_text(&radical.alternate) {
+            Some(alternate) => write!(
+                out,
+                "{} {} {} : {}",
+                radical.glyph, radical.strokes, alternate, kanji
+            )?,
+            None => write!(out, "{} {} : {}", radical.glyph, radical.strokes, kanji)?,
+        }
+    }
+    Ok(())
+}
+
+pub fn write_rust(expansions: &[Membership], out: &mut dyn Write) -> io::Result<()> {
+    write_lines(
+        out,
+        &[
+            "use super::{Alternate, Membership};",
+            "",
+            "/// For each radical, a list of which kanji contain it from the `radkfile`",
+            "pub const MEMBERSHIPS: &[Membership] = &[",
+        ],
+    )?;
     for expansion in expansions {
-        lines.push("\tMembership {".to_string());
+        writeln!(out, "\tMembership {{")?;
         let radical = &expansion.radical;
-        lines.push(format!("\t\tradical: \'{}\',", radical.glyph));
-        lines.push(format!("\t\tstrokes: {},", radical.strokes));
-        lines.push("\t\tkanji: &[".to_string());
+        writeln!(out, "\t\tradical: \'{}\',", radical.glyph)?;
+        writeln!(out, "\t\tstrokes: {},", radical.strokes)?;
+        writeln!(
+            out,
+            "\t\talternate: {},",
+            rust_alternate(&radical.alternate)
+        )?;
+        writeln!(out, "\t\tkanji: &[")?;
         for glyph in &expansion.kanji {
-            lines.push(format!("\t\t\t\'{}\',", glyph));
+            writeln!(out, "\t\t\t\'{}\',", glyph)?;
         }
-        lines.push("\t\t],".to_string());
-        lines.push("\t},".to_string());
+        writeln!(out, "\t\t],")?;
+        writeln!(out, "\t}},")?;
     }
-    lines.push("];".to_string());
-    lines.join("\n")
+    write!(out, "];")
 }
 
-fn consolidate(expansions: Vec<Membership>) -> Vec<Membership> {
-    let mut consolidation: HashMap<Radical, HashSet<String>> = HashMap::new();
-    for expansion in expansions.into_iter() {
-        match consolidation.entry(expansion.radical) {
-            std::collections::hash_map::Entry::Occupied(mut entry) => {
-                entry.get_mut().extend(expansion.kanji);
-            }
-            std::collections::hash_map::Entry::Vacant(entry) => {
-                entry.insert(expansion.kanji.into_iter().collect());
-            }
-        }
+fn rust_alternate(alternate: &Alternate) -> String {
+    match alternate {
+        Alternate::Image(image) => format!("Alternate::Image(\"{}\")", image),
+        Alternate::Glyph(glyph) => format!("Alternate::Glyph(\'{}\')", glyph),
+        Alternate::None => "Alternate::None".to_string(),
     }
+}
 
-    let mut consolidation: Vec<_> = consolidation
-        .into_iter()
-        .map(|(radical, kanji)| {
-            let kanji: Vec<_> = kanji.into_iter().collect();
-            Membership { radical, kanji }
-        })
-        .collect();
+pub fn write_typescript(expansions: &[Membership], out: &mut dyn Write) -> io::Result<()> {
+    write_lines(
+        out,
+        &[
+            "/** A different way to display a radical */",
+            "export type Alternate =",
+            "\t| { readonly type: \"image\"; readonly value: string }",
+            "\t| { readonly type: \"glyph\"; readonly value: string }",
+            "\t| null;",
+            "",
+            "/** The kanji containing a radical */",
+            "export interface Membership {",
+            "\t/** The radical */",
+            "\treadonly radical: string;",
+            "\t/** The number of strokes in the radical */",
+            "\treadonly strokes: number;",
+            "\t/** A different way to display the radical */",
+            "\treadonly alternate: Alternate;",
+            "\t/** The kanji containing the radical */",
+            "\treadonly kanji: readonly string[];",
+            "}",
+            "",
+            "/** For each radical, a list of which kanji contain it from the `radkfile` */",
+            "export const MEMBERSHIPS: readonly Membership[] = [",
+        ],
+    )?;
+    for expansion in expansions {
+        let radical = &expansion.radical;
+        let alternate = match &radical.alternate {
+            Alternate::Image(image) => format!("{{ type: \"image\", value: {} }}", quoted(image)),
+            Alternate::Glyph(glyph) => format!("{{ type: \"glyph\", value: {} }}", quoted(glyph)),
+            Alternate::None => "null".to_string(),
+        };
+        let kanji: Vec<_> = expansion.kanji.iter().map(|k| quoted(k)).collect();
+        writeln!(out, "\t{{")?;
+        writeln!(out, "\t\tradical: {},", quoted(&radical.glyph))?;
+        writeln!(out, "\t\tstrokes: {},", radical.strokes)?;
+        writeln!(out, "\t\talternate: {},", alternate)?;
+        writeln!(out, "\t\tkanji: [{}],", kanji.join(", "))?;
+        writeln!(out, "\t}},")?;
+    }
+    writeln!(out, "];")
+}
 
-    for membership in consolidation.iter_mut() {
-        membership.kanji.sort();
+pub fn write_python(expansions: &[Membership], out: &mut dyn Write) -> io::Result<()> {
+    write_lines(
+        out,
+        &[
+            "\"\"\"Radical memberships from the radkfile\"\"\"",
+            "",
+            "from typing import NamedTuple, Optional, Tuple",
+            "",
+            "",
+            "class Alternate(NamedTuple):",
+            "    \"\"\"A different way to display a radical\"\"\"",
+            "",
+            "    # Either \"image\" or \"glyph\"",
+            "    kind: str",
+            "    value: str",
+            "",
+            "",
+            "class Membership(NamedTuple):",
+            "    \"\"\"The kanji containing a radical\"\"\"",
+            "",
+            "    radical: str",
+            "    strokes: int",
+            "    alternate: Optional[Alternate]",
+            "    kanji: Tuple[str, ...]",
+            "",
+            "",
+            "MEMBERSHIPS: Tuple[Membership, ...] = (",
+        ],
+    )?;
+    for expansion in expansions {
+        let radical = &expansion.radical;
+        let alternate = match &radical.alternate {
+            Alternate::Image(image) => format!("Alternate(\"image\", {})", quoted(image)),
+            Alternate::Glyph(glyph) => format!("Alternate(\"glyph\", {})", quoted(glyph)),
+            Alternate::None => "None".to_string(),
+        };
+        let kanji: Vec<_> = expansion.kanji.iter().map(|k| quoted(k)).collect();
+        writeln!(out, "    Membership(")?;
+        writeln!(out, "        {},", quoted(&radical.glyph))?;
+        writeln!(out, "        {},", radical.strokes)?;
+        writeln!(out, "        {},", alternate)?;
+        writeln!(out, "        {},", python_tuple(&kanji))?;
+        writeln!(out, "    ),")?;
     }
+    writeln!(out, ")")
+}
 
-    consolidation.sort_by(|l, r| {
-        if l.radical.strokes != r.radical.strokes {
-            l.radical.strokes.cmp(&r.radical.strokes)
-        } else if l.kanji.len() != r.kanji.len() {
-            l.kanji.len().cmp(&r.kanji.len()).reverse()
-        } else {
-            l.radical.glyph.cmp(&r.radical.glyph)
+pub fn write_c(expansions: &[Membership], out: &mut dyn Write) -> io::Result<()> {
+    write_lines(
+        out,
+        &[
+            "#ifndef KRADICAL_MEMBERSHIPS_H",
+            "#define KRADICAL_MEMBERSHIPS_H",
+            "",
+            "#include <stddef.h>",
+            "#include <string.h>",
+            "",
+            "/* The kind of a radical's alternate representation */",
+            "typedef enum {",
+            "\tKRADICAL_ALTERNATE_NONE,",
+            "\tKRADICAL_ALTERNATE_IMAGE,",
+            "\tKRADICAL_ALTERNATE_GLYPH,",
+            "} kradical_alternate_kind;",
+            "",
+            "/* The kanji containing a radical as UTF-8 strings */",
+            "typedef struct {",
+            "\tconst char *radical;",
+            "\tunsigned char strokes;",
+            "\tkradical_alternate_kind alternate_kind;",
+            "\t/* NULL when there is no alternate */",
+            "\tconst char *alternate;",
+            "\tconst char *const *kanji;",
+            "\tsize_t kanji_count;",
+            "} kradical_membership;",
+            "",
+        ],
+    )?;
+    // Empty initializers are invalid before C23, so empty lists get no
+    // array and point to NULL instead
+    for (i, expansion) in expansions.iter().enumerate() {
+        if expansion.kanji.is_empty() {
+            continue;
         }
-    });
+        let kanji: Vec<_> = expansion.kanji.iter().map(|k| c_string(k)).collect();
+        writeln!(
+            out,
+            "static const char *const kradical_kanji_{}[] = {{{}}};",
+            i,
+            kanji.join(", ")
+        )?;
+    }
+    write_lines(
+        out,
+        &[
+            "",
+            "/* For each radical, a list of which kanji contain it from the radkfile */",
+            "static const kradical_membership KRADICAL_MEMBERSHIPS[] = {",
+        ],
+    )?;
+    for (i, expansion) in expansions.iter().enumerate() {
+        let radical = &expansion.radical;
+        let (kind, alternate) = match &radical.alternate {
+            Alternate::Image(image) => ("KRADICAL_ALTERNATE_IMAGE", c_string(image)),
+            Alternate::Glyph(glyph) => ("KRADICAL_ALTERNATE_GLYPH", c_string(glyph)),
+            Alternate::None => ("KRADICAL_ALTERNATE_NONE", "NULL".to_string()),
+        };
+        let kanji = if expansion.kanji.is_empty() {
+            "NULL".to_string()
+        } else {
+            format!("kradical_kanji_{}", i)
+        };
+        writeln!(
+            out,
+            "\t{{{}, {}, {}, {}, {}, {}}},",
+            c_string(&radical.glyph),
+            radical.strokes,
+            kind,
+            alternate,
+            kanji,
+            expansion.kanji.len()
+        )?;
+    }
+    write_lines(
+        out,
+        &[
+            "};",
+            "",
+            "#define KRADICAL_MEMBERSHIP_COUNT \\",
+            "\t(sizeof(KRADICAL_MEMBERSHIPS) / sizeof(KRADICAL_MEMBERSHIPS[0]))",
+            "",
+            "/* Finds the membership of a UTF-8 radical, or returns NULL */",
+            "static inline const kradical_membership *kradical_find_membership(const char *radical) {",
+            "\tfor (size_t i = 0; i < KRADICAL_MEMBERSHIP_COUNT; i++) {",
+            "\t\tif (strcmp(KRADICAL_MEMBERSHIPS[i].radical, radical) == 0) {",
+            "\t\t\treturn &KRADICAL_MEMBERSHIPS[i];",
+            "\t\t}",
+            "\t}",
+            "\treturn NULL;",
+            "}",
+            "",
+            "#endif",
+        ],
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use kradical_parsing::test_support::membership;
 
-    consolidation
+    #[test]
+    fn c_points_empty_lists_to_null() {
+        let memberships = vec![membership("一", 1, &["亜"]), membership("ノ", 1, &[])];
+        let mut out = vec![];
+        write_c(&memberships, &mut out).unwrap();
+        let header = String::from_utf8(out).unwrap();
+        assert!(header.contains("kradical_kanji_0[] = {"));
+        assert!(!header.contains("kradical_kanji_1"));
+        assert!(header.contains("\", 1, KRADICAL_ALTERNATE_NONE, NULL, NULL, 0},\n};"));
+    }
 }
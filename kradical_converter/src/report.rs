@@ -0,0 +1,25 @@
+use std::fmt::Display;
+
+use crate::{
+    error::ConvertError,
+    json::{self, JsonStyle},
+    opts::ReportFormat,
+};
+use serde::Serialize;
+
+pub fn format<T: Serialize + Display>(
+    report: &T,
+    format: ReportFormat,
+) -> Result<String, ConvertError> {
+    match format {
+        ReportFormat::Text => Ok(report.to_string()),
+        ReportFormat::Json => {
+            json::to_json(report, JsonStyle::Pretty).map_err(ConvertError::Report)
+        }
+    }
+}
+
+/// Writes a heading with the number of items that follow
+pub fn heading(f: &mut std::fmt::Formatter<'_>, title: &str, count: usize) -> std::fmt::Result {
+    writeln!(f, "{} ({})", title, count)
+}
@@ -0,0 +1,239 @@
+use kradical_parsing::{
+    krad::Decomposition,
+    radk::{Alternate, Membership},
+};
+use rusqlite::{params, Connection, DatabaseName, Result};
+
+const SCHEMA: &str = "
+CREATE TABLE kanji (
+    id INTEGER PRIMARY KEY,
+    glyph TEXT NOT NULL UNIQUE
+);
+
+CREATE TABLE radical (
+    id INTEGER PRIMARY KEY,
+    glyph TEXT NOT NULL UNIQUE,
+    strokes INTEGER,
+    alternate_type TEXT CHECK (alternate_type IN ('glyph', 'image')),
+    alternate TEXT
+);
+
+CREATE TABLE kanji_radical (
+    kanji_id INTEGER NOT NULL REFERENCES kanji (id),
+    radical_id INTEGER NOT NULL REFERENCES radical (id),
+    position INTEGER,
+    PRIMARY KEY (kanji_id, radical_id)
+) WITHOUT ROWID;
+
+CREATE INDEX kanji_radical_by_radical ON kanji_radical (radical_id, kanji_id);
+CREATE INDEX radical_by_strokes ON radical (strokes);
+
+CREATE VIEW radicals_per_kanji AS
+SELECT
+    kanji,
+    count(*) AS radical_count,
+    group_concat(radical, ' ') AS radicals
+FROM (
+    SELECT kanji.id AS kanji_id, kanji.glyph AS kanji, radical.glyph AS radical
+    FROM kanji
+    JOIN kanji_radical ON kanji_radical.kanji_id = kanji.id
+    JOIN radical ON radical.id = kanji_radical.radical_id
+    ORDER BY kanji.id, kanji_radical.position IS NULL, kanji_radical.position, radical.id
+)
+GROUP BY kanji_id;
+
+CREATE VIEW kanji_per_radical AS
+SELECT
+    radical,
+    strokes,
+    count(*) AS kanji_count,
+    group_concat(kanji, ' ') AS kanji
+FROM (
+    SELECT
+        radical.id AS radical_id,
+        radical.glyph AS radical,
+        radical.strokes AS strokes,
+        kanji.glyph AS kanji
+    FROM radical
+    JOIN kanji_radical ON kanji_radical.radical_id = radical.id
+    JOIN kanji ON kanji.id = kanji_radical.kanji_id
+    ORDER BY radical.id, kanji.id
+)
+GROUP BY radical_id;
+";
+
+/// Builds a database in memory and returns the bytes of the database file
+struct Database {
+    connection: Connection,
+}
+
+impl Database {
+    fn new() -> Result<Self> {
+        let connection = Connection::open_in_memory()?;
+        connection.execute_batch(SCHEMA)?;
+        connection.execute_batch("BEGIN")?;
+        Ok(Self { connection })
+    }
+
+    fn kanji(&self, glyph: &str) -> Result<i64> {
+        self.connection
+            .prepare_cached("INSERT OR IGNORE INTO kanji (glyph) VALUES (?1)")?
+            .execute(params![glyph])?;
+        self.connection
+            .prepare_cached("SELECT id FROM kanji WHERE glyph = ?1")?
+            .query_row(params![glyph], |row| row.get(0))
+    }
+
+    fn radical(&self, glyph: &str, strokes: Option<u8>, alternate: &Alternate) -> Result<i64> {
+        let (alternate_type, alternate) = match alternate {
+            Alternate::Image(image) => (Some("image"), Some(image)),
+            Alternate::Glyph(glyph) => (Some("glyph"), Some(glyph)),
+            Alternate::None => (None, None),
+        };
+        self.connection
+            .prepare_cached(
+                "INSERT OR IGNORE INTO radical (glyph, strokes, alternate_type, alternate)
+                VALUES (?1, ?2, ?3, ?4)",
+            )?
+            .execute(params![glyph, strokes, alternate_type, alternate])?;
+        self.connection
+            .prepare_cached("SELECT id FROM radical WHERE glyph = ?1")?
+            .query_row(params![glyph], |row| row.get(0))
+    }
+
+    fn link(&self, kanji: i64, radical: i64, position: Option<usize>) -> Result<()> {
+        self.connection
+            .prepare_cached(
+                "INSERT INTO kanji_radical (kanji_id, radical_id, position)
+                VALUES (?1, ?2, ?3)
+                ON CONFLICT (kanji_id, radical_id)
+                DO UPDATE SET position = coalesce(position, excluded.position)",
+            )?
+            .execute(params![kanji, radical, position.map(|i| i as i64)])?;
+        Ok(())
+    }
+
+    fn decompositions(&self, decompositions: &[Decomposition]) -> Result<()> {
+        for decomposition in decompositions {
+            let kanji = self.kanji(&decomposition.kanji)?;
+            for (i, radical) in decomposition.radicals.iter().enumerate() {
+                let radical = self.radical(radical, None, &Alternate::None)?;
+                self.link(kanji, radical, Some(i))?;
+            }
+        }
+        Ok(())
+    }
+
+    fn memberships(&self, memberships: &[Membership]) -> Result<()> {
+        for membership in memberships {
+            let radical = &membership.radical;
+            let radical =
+                self.radical(&radical.glyph, Some(radical.strokes), &radical.alternate)?;
+            for kanji in membership.kanji.iter() {
+                let kanji = self.kanji(kanji)?;
+                self.link(kanji, radical, None)?;
+            }
+        }
+        Ok(())
+    }
+
+    fn into_bytes(self) -> Result<Vec<u8>> {
+        self.connection.execute_batch("COMMIT")?;
+        Ok(self.connection.serialize(DatabaseName::Main)?.to_vec())
+    }
+}
+
+pub fn from_decompositions(decompositions: &[Decomposition]) -> Result<Vec<u8>> {
+    let database = Database::new()?;
+    database.decompositions(decompositions)?;
+    database.into_bytes()
+}
+
+pub fn from_memberships(memberships: &[Membership]) -> Result<Vec<u8>> {
+    let database = Database::new()?;
+    database.memberships(memberships)?;
+    database.into_bytes()
+}
+
+pub fn from_dataset(
+    decompositions: &[Decomposition],
+    memberships: &[Membership],
+) -> Result<Vec<u8>> {
+    let database = Database::new()?;
+    // Only the first insertion of a radical is kept,
+    // so add the ones with stroke counts first
+    database.memberships(memberships)?;
+    database.decompositions(decompositions)?;
+    database.into_bytes()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use kradical_parsing::test_support::{decomposition, membership};
+    use rusqlite::{ffi, serialize::OwnedData};
+    use std::ptr::NonNull;
+
+    /// Opens the database in memory, without writing it to a file
+    fn open(bytes: &[u8]) -> Connection {
+        // SQLite frees the copy with the connection, so it must come from
+        // its own allocator
+        let data = unsafe {
+            let copy = NonNull::new(ffi::sqlite3_malloc64(bytes.len() as u64) as *mut u8).unwrap();
+            std::ptr::copy_nonoverlapping(bytes.as_ptr(), copy.as_ptr(), bytes.len());
+            OwnedData::from_raw_nonnull(copy, bytes.len())
+        };
+        let mut connection = Connection::open_in_memory().unwrap();
+        connection
+            .deserialize(DatabaseName::Main, data, true)
+            .unwrap();
+        connection
+    }
+
+    #[test]
+    fn decompositions_round_trip() {
+        let decompositions = vec![decomposition("亜", &["｜", "一", "口"])];
+        let connection = open(&from_decompositions(&decompositions).unwrap());
+        let radicals: String = connection
+            .query_row(
+                "SELECT radicals FROM radicals_per_kanji WHERE kanji = '亜'",
+                [],
+                |row| row.get(0),
+            )
+            .unwrap();
+        assert_eq!(radicals, "｜ 一 口");
+    }
+
+    #[test]
+    fn radicals_keep_their_positions() {
+        let decompositions = vec![
+            decomposition("亜", &["一", "口"]),
+            decomposition("叶", &["十", "口", "一"]),
+        ];
+        let connection = open(&from_decompositions(&decompositions).unwrap());
+        let radicals: String = connection
+            .query_row(
+                "SELECT radicals FROM radicals_per_kanji WHERE kanji = '叶'",
+                [],
+                |row| row.get(0),
+            )
+            .unwrap();
+        assert_eq!(radicals, "十 口 一");
+    }
+
+    #[test]
+    fn memberships_keep_radical_metadata() {
+        let mut memberships = vec![membership("⻌", 3, &["込", "迎"])];
+        memberships[0].radical.alternate = Alternate::Glyph("辶".to_string());
+        let connection = open(&from_memberships(&memberships).unwrap());
+        let row: (u8, String, String, u32) = connection
+            .query_row(
+                "SELECT radical.strokes, alternate_type, alternate, kanji_count
+                FROM radical JOIN kanji_per_radical ON kanji_per_radical.radical = radical.glyph",
+                [],
+                |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?, row.get(3)?)),
+            )
+            .unwrap();
+        assert_eq!(row, (3, "glyph".to_string(), "辶".to_string(), 2));
+    }
+}
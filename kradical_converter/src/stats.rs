@@ -0,0 +1,251 @@
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    fmt::{self, Display, Formatter},
+};
+
+use crate::report::heading;
+use kradical_parsing::{bmp, krad::Decomposition, radk::Membership};
+use serde::Serialize;
+
+/// Summary figures for sanity-checking a release of the files
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Stats {
+    /// Counts for each input file
+    pub files: Vec<FileStats>,
+
+    /// The number of kanji having each number of radicals
+    pub radicals_per_kanji: BTreeMap<usize, usize>,
+
+    /// The radicals in the most kanji
+    pub most_common: Vec<RadicalCount>,
+
+    /// The radicals in the fewest kanji
+    pub least_common: Vec<RadicalCount>,
+
+    /// Radicals contained in only one kanji
+    pub single_member: Vec<RadicalMember>,
+
+    /// Kanji that decompose into the same set of radicals
+    pub identical_decompositions: Vec<IdenticalDecomposition>,
+
+    /// Kanji and radicals outside the Basic Multilingual Plane
+    pub outside_bmp: Vec<String>,
+}
+
+/// The number of kanji and radicals in an input file
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct FileStats {
+    pub path: String,
+    pub kanji: usize,
+    pub radicals: usize,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct RadicalCount {
+    pub radical: String,
+    pub kanji: usize,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct RadicalMember {
+    pub radical: String,
+    pub kanji: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct IdenticalDecomposition {
+    pub radicals: Vec<String>,
+    pub kanji: Vec<String>,
+}
+
+impl FileStats {
+    pub fn from_decompositions(path: &str, decompositions: &[Decomposition]) -> Self {
+        let radicals: BTreeSet<_> = decompositions
+            .iter()
+            .flat_map(|decomposition| decomposition.radicals.iter())
+            .collect();
+        Self {
+            path: path.to_string(),
+            kanji: decompositions.len(),
+            radicals: radicals.len(),
+        }
+    }
+
+    pub fn from_memberships(path: &str, memberships: &[Membership]) -> Self {
+        let kanji: BTreeSet<_> = memberships
+            .iter()
+            .flat_map(|membership| membership.kanji.iter())
+            .collect();
+        Self {
+            path: path.to_string(),
+            kanji: kanji.len(),
+            radicals: memberships.len(),
+        }
+    }
+}
+
+impl Stats {
+    /// Gathers the figures, with `top` radicals in each of the most
+    /// and least common lists
+    pub fn new(
+        files: Vec<FileStats>,
+        decompositions: &[Decomposition],
+        memberships: &[Membership],
+        top: usize,
+    ) -> Self {
+        let mut radicals_per_kanji = BTreeMap::new();
+        for decomposition in decompositions {
+            *radicals_per_kanji
+                .entry(decomposition.radicals.len())
+                .or_default() += 1;
+        }
+
+        let mut counts: Vec<_> = memberships
+            .iter()
+            .map(|membership| RadicalCount {
+                radical: membership.radical.glyph.clone(),
+                kanji: membership.kanji.len(),
+            })
+            .collect();
+        counts.sort_by(|l, r| r.kanji.cmp(&l.kanji).then(l.radical.cmp(&r.radical)));
+        let most_common = counts.iter().take(top).cloned().collect();
+        let least_common = counts.iter().rev().take(top).cloned().collect();
+
+        let single_member = memberships
+            .iter()
+            .filter(|membership| membership.kanji.len() == 1)
+            .map(|membership| RadicalMember {
+                radical: membership.radical.glyph.clone(),
+                kanji: membership.kanji[0].clone(),
+            })
+            .collect();
+
+        let mut groups: BTreeMap<BTreeSet<&String>, Vec<String>> = BTreeMap::new();
+        for decomposition in decompositions {
+            groups
+                .entry(decomposition.radicals.iter().collect())
+                .or_default()
+                .push(decomposition.kanji.clone());
+        }
+        let identical_decompositions = groups
+            .into_iter()
+            .filter(|(_, kanji)| kanji.len() > 1)
+            .map(|(radicals, kanji)| IdenticalDecomposition {
+                radicals: radicals.into_iter().cloned().collect(),
+                kanji,
+            })
+            .collect();
+
+        let glyphs = decompositions
+            .iter()
+            .flat_map(|decomposition| {
+                std::iter::once(&decomposition.kanji).chain(decomposition.radicals.iter())
+            })
+            .chain(memberships.iter().flat_map(|membership| {
+                std::iter::once(&membership.radical.glyph).chain(membership.kanji.iter())
+            }));
+        let outside_bmp: BTreeSet<_> = glyphs
+            .filter(|glyph| !glyph.chars().all(bmp::is_bmp))
+            .cloned()
+            .collect();
+
+        Self {
+            files,
+            radicals_per_kanji,
+            most_common,
+            least_common,
+            single_member,
+            identical_decompositions,
+            outside_bmp: outside_bmp.into_iter().collect(),
+        }
+    }
+}
+
+impl Display for Stats {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        heading(f, "Files", self.files.len())?;
+        for file in self.files.iter() {
+            writeln!(
+                f,
+                "  {} : {} kanji, {} radicals",
+                file.path, file.kanji, file.radicals
+            )?;
+        }
+        heading(f, "Radicals per kanji", self.radicals_per_kanji.len())?;
+        for (radicals, kanji) in self.radicals_per_kanji.iter() {
+            writeln!(f, "  {} : {} kanji", radicals, kanji)?;
+        }
+        heading(f, "Most common radicals", self.most_common.len())?;
+        counts(f, &self.most_common)?;
+        heading(f, "Least common radicals", self.least_common.len())?;
+        counts(f, &self.least_common)?;
+        heading(f, "Radicals with a single kanji", self.single_member.len())?;
+        for member in self.single_member.iter() {
+            writeln!(f, "  {} : {}", member.radical, member.kanji)?;
+        }
+        heading(
+            f,
+            "Identical decompositions",
+            self.identical_decompositions.len(),
+        )?;
+        for group in self.identical_decompositions.iter() {
+            writeln!(
+                f,
+                "  {} : {}",
+                group.radicals.join(" "),
+                group.kanji.join(" ")
+            )?;
+        }
+        heading(f, "Outside the BMP", self.outside_bmp.len())?;
+        if !self.outside_bmp.is_empty() {
+            writeln!(f, "  {}", self.outside_bmp.join(" "))?;
+        }
+        Ok(())
+    }
+}
+
+fn counts(f: &mut Formatter<'_>, counts: &[RadicalCount]) -> fmt::Result {
+    for count in counts {
+        writeln!(f, "  {} : {} kanji", count.radical, count.kanji)?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use kradical_parsing::test_support::{decomposition, membership};
+
+    #[test]
+    fn gathers_figures() {
+        let decompositions = [
+            decomposition("亜", &["｜", "一", "口"]),
+            decomposition("唖", &["口", "｜", "一"]),
+            decomposition("𠆢", &["𠆢"]),
+        ];
+        let memberships = [
+            membership("一", 1, &["亜", "唖"]),
+            membership("口", 1, &["亜", "唖", "叶"]),
+            membership("𠆢", 1, &["𠆢"]),
+        ];
+        let stats = Stats::new(vec![], &decompositions, &memberships, 1);
+        assert_eq!(
+            stats.radicals_per_kanji,
+            vec![(1, 1), (3, 2)].into_iter().collect()
+        );
+        assert_eq!(stats.most_common[0].radical, "口");
+        assert_eq!(stats.least_common[0].radical, "𠆢");
+        assert_eq!(
+            stats.single_member,
+            vec![RadicalMember {
+                radical: "𠆢".to_string(),
+                kanji: "𠆢".to_string()
+            }]
+        );
+        assert_eq!(
+            stats.identical_decompositions[0].kanji,
+            vec!["亜".to_string(), "唖".to_string()]
+        );
+        assert_eq!(stats.outside_bmp, vec!["𠆢".to_string()]);
+    }
+}
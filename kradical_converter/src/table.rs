@@ -0,0 +1,58 @@
+use csv::{Terminator, WriterBuilder};
+use std::io::Write;
+
+/// Field separator for tabular outputs
+#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+pub enum Delimiter {
+    /// Comma-separated values following RFC 4180
+    Comma,
+
+    /// Tab-separated values with the same quoting rules
+    Tab,
+}
+
+pub fn write_table<R>(
+    header: &[&str],
+    rows: R,
+    delimiter: Delimiter,
+    out: &mut dyn Write,
+) -> Result<(), csv::Error>
+where
+    R: IntoIterator<Item = Vec<String>>,
+{
+    let (delimiter, terminator) = match delimiter {
+        Delimiter::Comma => (b',', Terminator::CRLF),
+        Delimiter::Tab => (b'\t', Terminator::Any(b'\n')),
+    };
+    let mut writer = WriterBuilder::new()
+        .delimiter(delimiter)
+        .terminator(terminator)
+        .from_writer(out);
+    writer.write_record(header)?;
+    for row in rows {
+        writer.write_record(&row)?;
+    }
+    writer.flush()?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn quotes_fields() {
+        let rows = vec![vec!["a,b".to_string(), "\"c\"".to_string()]];
+        let mut res = vec![];
+        write_table(&["x", "y"], rows, Delimiter::Comma, &mut res).unwrap();
+        assert_eq!(res, "x,y\r\n\"a,b\",\"\"\"c\"\"\"\r\n".as_bytes());
+    }
+
+    #[test]
+    fn separates_with_tabs() {
+        let rows = vec![vec!["一".to_string(), "1".to_string()]];
+        let mut res = vec![];
+        write_table(&["radical", "strokes"], rows, Delimiter::Tab, &mut res).unwrap();
+        assert_eq!(res, "radical\tstrokes\n一\t1\n".as_bytes());
+    }
+}
@@ -0,0 +1,77 @@
+use std::io::{Cursor, Write};
+
+use crate::error::FormatError;
+use kradical_parsing::krad::Decomposition;
+use serde::Serialize;
+use serde_json::{json, Value};
+use zip::{write::SimpleFileOptions, CompressionMethod, ZipWriter};
+
+/// The number of kanji in each `kanji_bank` file
+const BANK_SIZE: usize = 2000;
+
+/// Describes the dictionary to Yomitan
+#[derive(Serialize)]
+struct Index {
+    title: &'static str,
+    revision: &'static str,
+    format: u8,
+    sequenced: bool,
+    author: &'static str,
+    attribution: &'static str,
+    description: &'static str,
+}
+
+/// A Yomitan dictionary archive whose kanji entries list their radicals
+pub fn to_archive(decompositions: &[Decomposition]) -> Result<Vec<u8>, FormatError> {
+    let options = SimpleFileOptions::default().compression_method(CompressionMethod::Deflated);
+    let mut archive = ZipWriter::new(Cursor::new(vec![]));
+
+    let index = Index {
+        title: "Kradical radicals",
+        revision: concat!("kradical_converter ", env!("CARGO_PKG_VERSION")),
+        format: 3,
+        sequenced: false,
+        author: "EDRDG",
+        attribution: "Adapted from the EDRDG kradfile and kradfile2 under CC BY-SA 3.0",
+        description: "The radicals that make up each kanji",
+    };
+    archive.start_file("index.json", options)?;
+    archive.write_all(&serde_json::to_vec(&index)?)?;
+
+    for (i, bank) in decompositions.chunks(BANK_SIZE).enumerate() {
+        let entries: Vec<_> = bank.iter().map(entry).collect();
+        archive.start_file(format!("kanji_bank_{}.json", i + 1), options)?;
+        archive.write_all(&serde_json::to_vec(&entries)?)?;
+    }
+
+    Ok(archive.finish()?.into_inner())
+}
+
+/// A kanji entry, which holds the character, onyomi, kunyomi, tags,
+/// meanings and stats. Each radical is listed as a meaning.
+fn entry(decomposition: &Decomposition) -> Value {
+    json!([decomposition.kanji, "", "", "", decomposition.radicals, {}])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use kradical_parsing::test_support::decomposition;
+    use std::io::Read;
+    use zip::ZipArchive;
+
+    #[test]
+    fn writes_index_and_banks() {
+        let decompositions = vec![decomposition("亜", &["｜", "一", "口"])];
+        let bytes = to_archive(&decompositions).unwrap();
+        let mut archive = ZipArchive::new(Cursor::new(bytes)).unwrap();
+        let mut bank = String::new();
+        archive
+            .by_name("kanji_bank_1.json")
+            .unwrap()
+            .read_to_string(&mut bank)
+            .unwrap();
+        assert_eq!(bank, r#"[["亜","","","",["｜","一","口"],{}]]"#);
+        assert!(archive.by_name("index.json").is_ok());
+    }
+}
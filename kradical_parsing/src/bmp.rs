@@ -0,0 +1,88 @@
+//! Fallbacks for software limited to the Basic Multilingual Plane.
+//!
+//! A few radical replacements are outside the BMP, which UCS-2 systems and
+//! many fonts cannot handle. Each has a documented fallback inside it.
+
+use crate::{
+    krad::Decomposition,
+    radk::{Alternate, Membership},
+};
+use thiserror::Error;
+
+#[cfg(test)]
+mod tests;
+
+/// Enumerates the module's possible errors
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+pub enum BmpError {
+    /// A character outside the BMP has no fallback
+    #[error("No fallback for {0} (U+{:X}) in the Basic Multilingual Plane", *.0 as u32)]
+    NoFallback(char),
+}
+
+/// Characters outside the BMP and their fallbacks, which are the
+/// characters the EDRDG files use for the same radicals
+pub const FALLBACKS: &[(char, char)] = &[
+    // The radical replacement for 个
+    ('\u{201A2}', '个'),
+    // The radical replacement for 乞
+    ('\u{20089}', '乞'),
+    // The unified ideograph for ⺌, which the EDRDG files write as 尚
+    ('\u{2D544}', '尚'),
+];
+
+/// Whether a character is in the Basic Multilingual Plane
+pub fn is_bmp(c: char) -> bool {
+    (c as u32) <= 0xFFFF
+}
+
+/// The fallback for a character outside the BMP
+pub fn fallback(c: char) -> Option<char> {
+    FALLBACKS
+        .iter()
+        .find(|(outside, _)| *outside == c)
+        .map(|(_, fallback)| *fallback)
+}
+
+/// Replaces each character outside the BMP with its fallback
+pub fn to_bmp(text: &str) -> Result<String, BmpError> {
+    text.chars()
+        .map(|c| match is_bmp(c) {
+            true => Ok(c),
+            false => fallback(c).ok_or(BmpError::NoFallback(c)),
+        })
+        .collect()
+}
+
+/// Replaces the characters outside the BMP in decompositions
+pub fn decompositions(decompositions: &mut [Decomposition]) -> Result<(), BmpError> {
+    for decomposition in decompositions.iter_mut() {
+        replace(&mut decomposition.kanji)?;
+        for radical in decomposition.radicals.iter_mut() {
+            replace(radical)?;
+        }
+    }
+    Ok(())
+}
+
+/// Replaces the characters outside the BMP in memberships,
+/// including alternate glyphs
+pub fn memberships(memberships: &mut [Membership]) -> Result<(), BmpError> {
+    for membership in memberships.iter_mut() {
+        replace(&mut membership.radical.glyph)?;
+        if let Alternate::Glyph(glyph) = &mut membership.radical.alternate {
+            replace(glyph)?;
+        }
+        for kanji in membership.kanji.iter_mut() {
+            replace(kanji)?;
+        }
+    }
+    Ok(())
+}
+
+fn replace(text: &mut String) -> Result<(), BmpError> {
+    if !text.chars().all(is_bmp) {
+        *text = to_bmp(text)?;
+    }
+    Ok(())
+}
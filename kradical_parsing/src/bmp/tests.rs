@@ -0,0 +1,28 @@
+use super::*;
+use crate::test_support::{decomposition, membership};
+
+#[test]
+fn replaces_radicals() {
+    let mut parsed = vec![decomposition("乞", &["\u{20089}", "乙"])];
+    decompositions(&mut parsed).unwrap();
+    assert_eq!(parsed[0].radicals, vec!["乞", "乙"]);
+
+    let mut parsed = vec![membership("\u{201A2}", 2, &["今"])];
+    parsed[0].radical.alternate = Alternate::Image("js02".to_string());
+    memberships(&mut parsed).unwrap();
+    assert_eq!(parsed[0].radical.glyph, "个");
+}
+
+#[test]
+fn rejects_missing_fallbacks() {
+    assert_eq!(to_bmp("亜"), Ok("亜".to_string()));
+    assert_eq!(to_bmp("\u{20000}"), Err(BmpError::NoFallback('\u{20000}')));
+}
+
+#[test]
+fn fallbacks_are_in_bmp() {
+    for (outside, fallback) in FALLBACKS {
+        assert!(!is_bmp(*outside));
+        assert!(is_bmp(*fallback));
+    }
+}
@@ -0,0 +1,83 @@
+//! Conversions between decompositions and memberships.
+//!
+//! The `kradfile`s and `radkfile`s describe the same relation from either
+//! side, so each can be regenerated from the other.
+
+use crate::{
+    krad::Decomposition,
+    radk::{Membership, Radical},
+};
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+
+#[cfg(test)]
+mod tests;
+
+/// Gathers the kanji containing each radical from a list of decompositions
+///
+/// # Arguments
+///
+/// * `decompositions` - The decompositions to invert
+/// * `radicals` - The radical table supplying stroke counts and alternates.
+///   Memberships follow its order, and radicals missing from it are skipped.
+pub fn memberships(decompositions: &[Decomposition], radicals: &[Radical]) -> Vec<Membership> {
+    let mut kanji: HashMap<&str, BTreeSet<&str>> = HashMap::new();
+    for decomposition in decompositions {
+        for radical in decomposition.radicals.iter() {
+            kanji
+                .entry(radical)
+                .or_default()
+                .insert(&decomposition.kanji);
+        }
+    }
+    radicals
+        .iter()
+        .filter_map(|radical| {
+            let kanji = kanji.get(radical.glyph.as_str())?;
+            Some(Membership {
+                radical: radical.clone(),
+                kanji: kanji.iter().map(|kanji| kanji.to_string()).collect(),
+            })
+        })
+        .collect()
+}
+
+/// Lists the radicals used by the decompositions that are not in the radical table
+pub fn unknown_radicals(decompositions: &[Decomposition], radicals: &[Radical]) -> Vec<String> {
+    let known: BTreeSet<_> = radicals.iter().map(|radical| &radical.glyph).collect();
+    let unknown: BTreeSet<_> = decompositions
+        .iter()
+        .flat_map(|decomposition| decomposition.radicals.iter())
+        .filter(|radical| !known.contains(radical))
+        .collect();
+    unknown.into_iter().cloned().collect()
+}
+
+/// Gathers the radicals of each kanji from a list of memberships.
+/// Kanji are sorted and their radicals follow the order of the memberships.
+pub fn decompositions(memberships: &[Membership]) -> Vec<Decomposition> {
+    let mut radicals: BTreeMap<&str, Vec<String>> = BTreeMap::new();
+    for membership in memberships {
+        for kanji in membership.kanji.iter() {
+            let kanji_radicals = radicals.entry(kanji).or_default();
+            if !kanji_radicals.contains(&membership.radical.glyph) {
+                kanji_radicals.push(membership.radical.glyph.clone());
+            }
+        }
+    }
+    radicals
+        .into_iter()
+        .map(|(kanji, radicals)| Decomposition {
+            kanji: kanji.to_string(),
+            radicals,
+        })
+        .collect()
+}
+
+/// Lists the radicals of a radkfile, which serve as the radical table
+/// for [`memberships`]
+pub fn radicals(memberships: &[Membership]) -> Vec<Radical> {
+    memberships
+        .iter()
+        .map(|membership| membership.radical.clone())
+        .collect()
+}
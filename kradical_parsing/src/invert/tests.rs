@@ -0,0 +1,52 @@
+use super::*;
+use crate::test_support::{decomposition, membership, radical};
+
+#[test]
+fn inverts_decompositions() {
+    let decompositions = [
+        decomposition("唖", &["｜", "一", "口"]),
+        decomposition("亜", &["｜", "一", "口"]),
+        decomposition("悒", &["心", "邑"]),
+    ];
+    let radicals = [
+        radical("一", 1),
+        radical("｜", 1),
+        radical("口", 3),
+        radical("心", 4),
+    ];
+    assert_eq!(
+        memberships(&decompositions, &radicals),
+        vec![
+            membership("一", 1, &["亜", "唖"]),
+            membership("｜", 1, &["亜", "唖"]),
+            membership("口", 3, &["亜", "唖"]),
+            membership("心", 4, &["悒"]),
+        ]
+    );
+    assert_eq!(unknown_radicals(&decompositions, &radicals), vec!["邑"]);
+}
+
+#[test]
+fn inverts_memberships() {
+    let memberships = [
+        membership("一", 1, &["唖", "亜"]),
+        membership("口", 3, &["唖"]),
+    ];
+    assert_eq!(
+        decompositions(&memberships),
+        vec![
+            decomposition("亜", &["一"]),
+            decomposition("唖", &["一", "口"]),
+        ]
+    );
+}
+
+#[test]
+fn round_trips() {
+    let original = vec![
+        decomposition("亜", &["一", "｜", "口"]),
+        decomposition("唖", &["一", "｜", "口"]),
+    ];
+    let radicals = [radical("一", 1), radical("｜", 1), radical("口", 3)];
+    assert_eq!(decompositions(&memberships(&original, &radicals)), original);
+}
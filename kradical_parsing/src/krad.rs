@@ -1,6 +1,6 @@
 //! Parser for `kradfile` and `kradfile2`.
 
-use crate::shared::{comments, decode_jis_kanji, decode_jis_radical};
+use crate::shared::{comments, decode_jis_kanji, decode_jis_radical, error_line, line_number};
 use nom::{
     bytes::{
         complete::{tag, take_until},
@@ -22,8 +22,11 @@ mod tests;
 #[derive(Error, Debug)]
 pub enum KradError {
     /// Error while parsing kradfile
-    #[error("Error while parsing kradfile")]
-    Parse,
+    #[error("Error while parsing kradfile at line {line}")]
+    Parse {
+        /// The line that could not be parsed, counting from 1
+        line: usize,
+    },
 
     /// Error while reading kradfile
     #[error("Error while reading kradfile")]
@@ -68,7 +71,17 @@ fn parse_file_implementation(path: &Path) -> KradResult {
 ///
 /// * `b` - The bytes to parse
 pub fn parse_bytes(b: &[u8]) -> KradResult {
-    lines(b).map(|(_i, o)| o).map_err(|_err| KradError::Parse)
+    match lines(b) {
+        Ok((rest, decompositions)) if rest.iter().all(u8::is_ascii_whitespace) => {
+            Ok(decompositions)
+        }
+        Ok((rest, _)) => Err(KradError::Parse {
+            line: line_number(b, rest.strip_prefix(b"\n").unwrap_or(rest)),
+        }),
+        Err(err) => Err(KradError::Parse {
+            line: error_line(b, &err),
+        }),
+    }
 }
 
 fn lines(b: &[u8]) -> IResult<&[u8], Vec<Decomposition>> {
@@ -63,14 +63,14 @@ fn parses_line_as_kanji() {
 
 #[test]
 fn ignores_comment() {
-    let line = vec![COMMENT_LINE, KANJI_LINE].join(EMPTY);
+    let line = [COMMENT_LINE, KANJI_LINE].join(EMPTY);
     let res = next_kanji(&line);
     assert_eq!(res, Ok((NEWLINE, parsed_kanji())));
 }
 
 #[test]
 fn parses_lines() {
-    let line = vec![KANJI_LINE, COMMENT_LINE, KANJI_LINE].join(EMPTY);
+    let line = [KANJI_LINE, COMMENT_LINE, KANJI_LINE].join(EMPTY);
     let res = lines(&line);
     assert_eq!(res, Ok((NEWLINE, vec![parsed_kanji(), parsed_kanji()])));
 }
@@ -78,13 +78,22 @@ fn parses_lines() {
 #[test]
 fn works_on_actual_file() {
     let res = parse_file("../assets/edrdg_files/kradfile");
-    assert_eq!(res.is_ok(), true);
+    assert!(res.is_ok());
     assert_eq!(res.unwrap().len(), 6_355);
 }
 
 #[test]
 fn works_on_actual_file_2() {
     let res = parse_file("../assets/edrdg_files/kradfile2");
-    assert_eq!(res.is_ok(), true);
+    assert!(res.is_ok());
     assert_eq!(res.unwrap().len(), 5_801);
 }
+
+#[test]
+fn reports_invalid_line() {
+    let lines = [KANJI_LINE, b"invalid\n"].join("".as_bytes());
+    assert!(matches!(
+        parse_bytes(&lines),
+        Err(KradError::Parse { line: 2 })
+    ));
+}
@@ -2,8 +2,13 @@
 
 #[cfg(test)]
 mod test_constants;
+#[cfg(any(test, feature = "test-support"))]
+#[doc(hidden)]
+pub mod test_support;
 
 mod shared;
 
+pub mod bmp;
+pub mod invert;
 pub mod krad;
 pub mod radk;
@@ -1,14 +1,14 @@
 //! Parser for `radkfile` and `radkfile2`.
 
-use crate::shared::{comments, decode_jis_radical};
+use crate::shared::{comments, decode_jis_radical, error_line, line_number};
 use encoding::{codec::japanese::EUCJPEncoding, DecoderTrap, Encoding};
 use kradical_jis::jis212_to_utf8;
 use nom::{
     branch::alt,
     bytes::complete::{tag, take, take_while, take_while1, take_while_m_n},
     character::{complete::space0, is_alphanumeric, is_digit},
-    combinator::{eof, map, map_res, success, value},
-    multi::many_till,
+    combinator::{map, map_res, success, value},
+    multi::many0,
     sequence::{pair, separated_pair, terminated, tuple},
     IResult,
 };
@@ -34,12 +34,15 @@ pub enum RadkError {
     #[error("Invalid kanji line")]
     EucJp,
 
-    /// Error while parsing kradfile
-    #[error("Error while parsing kradfile")]
-    Parse,
+    /// Error while parsing radkfile
+    #[error("Error while parsing radkfile at line {line}")]
+    Parse {
+        /// The line that could not be parsed, counting from 1
+        line: usize,
+    },
 
-    /// Error while reading kradfile
-    #[error("Error while reading kradfile")]
+    /// Error while reading radkfile
+    #[error("Error while reading radkfile")]
     Io(#[from] std::io::Error),
 }
 
@@ -105,11 +108,19 @@ fn parse_file_implementation(path: &Path) -> RadkResult {
 ///
 /// * `b` - The bytes to parse
 pub fn parse_bytes(b: &[u8]) -> RadkResult {
-    lines(b).map(|(_i, o)| o).map_err(|_err| RadkError::Parse)
+    match lines(b) {
+        Ok((rest, memberships)) if rest.iter().all(u8::is_ascii_whitespace) => Ok(memberships),
+        Ok((rest, _)) => Err(RadkError::Parse {
+            line: line_number(b, rest),
+        }),
+        Err(err) => Err(RadkError::Parse {
+            line: error_line(b, &err),
+        }),
+    }
 }
 
 fn lines(b: &[u8]) -> IResult<&[u8], Vec<Membership>> {
-    map(many_till(kanji, eof), |(kanji, _)| kanji)(b)
+    terminated(many0(kanji), comments)(b)
 }
 
 fn kanji(b: &[u8]) -> IResult<&[u8], Membership> {
@@ -174,7 +185,7 @@ fn hex(b: &[u8]) -> IResult<&[u8], Alternate> {
 
 fn from_hex(b: &[u8]) -> Result<Alternate, RadkError> {
     let s = std::str::from_utf8(b).map_err(|_| RadkError::NotGlyph)?;
-    let code = u16::from_str_radix(&s, 16).map_err(|_| RadkError::NotGlyph)?;
+    let code = u16::from_str_radix(s, 16).map_err(|_| RadkError::NotGlyph)?;
     jis212_to_utf8(code)
         .ok_or(RadkError::NotGlyph)
         .map(|s| Alternate::Glyph(s.to_string()))
@@ -182,7 +193,7 @@ fn from_hex(b: &[u8]) -> Result<Alternate, RadkError> {
 
 fn is_hex_digit(b: u8) -> bool {
     let c = b as char;
-    (c.is_ascii_uppercase() || c.is_ascii_digit()) && c.is_digit(16)
+    (c.is_ascii_uppercase() || c.is_ascii_digit()) && c.is_ascii_hexdigit()
 }
 
 fn ident_line_token(b: &[u8]) -> IResult<&[u8], ()> {
@@ -74,7 +74,7 @@ fn image_ident_line() {
         Ok((
             EMPTY,
             Radical {
-                glyph: "ㅅ".to_string(),
+                glyph: "𠆢".to_string(),
                 strokes: 2,
                 alternate: Alternate::Image("js02".to_string()),
             }
@@ -201,7 +201,7 @@ fn works_on_actual_file() {
         assert_eq!(inclusions.len(), 253);
     } else {
         println!("{:?}", res);
-        assert_eq!(true, res.is_ok());
+        assert!(res.is_ok());
     }
 }
 
@@ -212,6 +212,24 @@ fn works_on_actual_file_2() {
         assert_eq!(inclusions.len(), 253);
     } else {
         println!("{:?}", res);
-        assert_eq!(true, res.is_ok());
+        assert!(res.is_ok());
     }
 }
+
+#[test]
+fn reports_invalid_line() {
+    let lines = [COMMENT_LINE, b"$ invalid\n"].join("".as_bytes());
+    assert!(matches!(
+        super::parse_bytes(&lines),
+        Err(super::RadkError::Parse { line: 2 })
+    ));
+}
+
+#[test]
+fn reports_trailing_input() {
+    let lines = [FULL_KANJI, b"\ninvalid\n"].join("".as_bytes());
+    assert!(matches!(
+        super::parse_bytes(&lines),
+        Err(super::RadkError::Parse { line: 5 })
+    ));
+}
@@ -229,6 +229,21 @@ pub fn decode_jis_kanji(b: &[u8]) -> Result<String, SharedError> {
     }
 }
 
+/// The line on which `rest`, the unparsed end of `b`, begins
+pub fn line_number(b: &[u8], rest: &[u8]) -> usize {
+    let offset = b.len() - rest.len();
+    b[..offset].iter().filter(|&&byte| byte == b'\n').count() + 1
+}
+
+/// The line on which a parser failed, or the last line
+/// if the parser needed more input
+pub fn error_line(b: &[u8], err: &nom::Err<nom::error::Error<&[u8]>>) -> usize {
+    match err {
+        nom::Err::Error(err) | nom::Err::Failure(err) => line_number(b, err.input),
+        nom::Err::Incomplete(_) => line_number(b, &[]),
+    }
+}
+
 fn bytes_to_u32(b: &[u8]) -> u32 {
     let mut out = 0u32;
     for (i, byte) in b.iter().rev().enumerate() {
@@ -257,7 +272,7 @@ mod tests {
 
     #[test]
     fn multiple_comment_lines() {
-        let line = vec![COMMENT_LINE, COMMENT_LINE].join("".as_bytes());
+        let line = [COMMENT_LINE, COMMENT_LINE].join("".as_bytes());
         let res = comments(&line);
         assert_eq!(res, Ok((EMPTY, ())));
     }
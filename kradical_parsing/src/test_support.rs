@@ -0,0 +1,32 @@
+//! Builders for parsed data in tests, shared with the converter through
+//! the `test-support` feature
+
+use crate::{
+    krad::Decomposition,
+    radk::{Alternate, Membership, Radical},
+};
+
+/// A kanji made of the given radicals
+pub fn decomposition(kanji: &str, radicals: &[&str]) -> Decomposition {
+    Decomposition {
+        kanji: kanji.to_string(),
+        radicals: radicals.iter().map(|s| s.to_string()).collect(),
+    }
+}
+
+/// A radical without an alternate
+pub fn radical(glyph: &str, strokes: u8) -> Radical {
+    Radical {
+        glyph: glyph.to_string(),
+        strokes,
+        alternate: Alternate::None,
+    }
+}
+
+/// The kanji containing a radical without an alternate
+pub fn membership(radical_glyph: &str, strokes: u8, kanji: &[&str]) -> Membership {
+    Membership {
+        radical: radical(radical_glyph, strokes),
+        kanji: kanji.iter().map(|s| s.to_string()).collect(),
+    }
+}
@@ -3,9 +3,11 @@
 
 mod decompositions;
 mod memberships;
+mod repertoire;
 
 pub use decompositions::*;
 pub use memberships::*;
+pub use repertoire::*;
 
 /// The constituent radicals for a kanji
 pub struct Decomposition {
@@ -26,4 +28,19 @@ pub struct Membership {
 
     /// The number of strokes to draw the radical
     pub strokes: u8,
+
+    /// An alternate representation of the radical
+    pub alternate: Alternate,
+}
+
+/// Alternate representations for a radical other than the UTF-8 glyph
+pub enum Alternate {
+    /// The name of an image from the WWWJDIC website
+    Image(&'static str),
+
+    /// Another glyph that better depicts the radical
+    Glyph(char),
+
+    /// No alternate representation provided
+    None,
 }
@@ -0,0 +1,1334 @@
+//! Equivalent ways of writing each radical. The parsed radicals mix unified
+//! ideographs, Kangxi Radicals and CJK Radicals Supplement characters, so
+//! this table converts them to a single repertoire that suits the font in use.
+
+#[cfg(test)]
+mod tests;
+
+/// A set of characters for writing radicals
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Repertoire {
+    /// The characters of the EDRDG files, which stand in for some radicals
+    /// with a kanji containing them or with katakana
+    Original,
+
+    /// CJK Unified Ideographs
+    Unified,
+
+    /// Kangxi Radicals and CJK Radicals Supplement, keeping the unified
+    /// ideograph for radicals in neither block
+    Radicals,
+}
+
+/// The characters for one radical in each repertoire
+///
+/// Characters are never shared between radicals, so a radical keeps its
+/// glyph where the equivalent is taken by another. For example, ⻏ has no
+/// unified ideograph because ⻖ maps to 阝.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Equivalent {
+    pub original: char,
+    pub unified: char,
+    pub radical: char,
+}
+
+impl Equivalent {
+    /// The character for the radical in a repertoire
+    pub fn get(&self, repertoire: Repertoire) -> char {
+        match repertoire {
+            Repertoire::Original => self.original,
+            Repertoire::Unified => self.unified,
+            Repertoire::Radicals => self.radical,
+        }
+    }
+
+    fn contains(&self, glyph: char) -> bool {
+        self.original == glyph || self.unified == glyph || self.radical == glyph
+    }
+}
+
+/// Finds the equivalents of a radical written in any repertoire
+pub fn equivalent(glyph: char) -> Option<&'static Equivalent> {
+    EQUIVALENTS
+        .iter()
+        .find(|equivalent| equivalent.contains(glyph))
+}
+
+/// Writes a radical in the given repertoire. Characters in none of the
+/// repertoires are returned unchanged.
+///
+/// The original repertoire includes kanji that the EDRDG files use in place
+/// of a radical, so those kanji are remapped too: 化 becomes 亻 in the
+/// unified repertoire. Only pass radicals, not arbitrary text.
+pub fn normalize(glyph: char, repertoire: Repertoire) -> char {
+    equivalent(glyph).map_or(glyph, |equivalent| equivalent.get(repertoire))
+}
+
+/// The equivalents of every radical in the radkfiles, in the same order
+pub const EQUIVALENTS: &[Equivalent] = &[
+    Equivalent {
+        original: '一',
+        unified: '一',
+        radical: '⼀',
+    },
+    Equivalent {
+        original: 'ノ',
+        unified: '丿',
+        radical: '⼃',
+    },
+    Equivalent {
+        original: '｜',
+        unified: '丨',
+        radical: '⼁',
+    },
+    Equivalent {
+        original: '丶',
+        unified: '丶',
+        radical: '⼂',
+    },
+    Equivalent {
+        original: '亅',
+        unified: '亅',
+        radical: '⼅',
+    },
+    Equivalent {
+        original: '乙',
+        unified: '乙',
+        radical: '⼄',
+    },
+    Equivalent {
+        original: 'ハ',
+        unified: '八',
+        radical: '⼋',
+    },
+    Equivalent {
+        original: '二',
+        unified: '二',
+        radical: '⼆',
+    },
+    Equivalent {
+        original: '十',
+        unified: '十',
+        radical: '⼗',
+    },
+    Equivalent {
+        original: '并',
+        unified: '丷',
+        radical: '⺍',
+    },
+    Equivalent {
+        original: '亠',
+        unified: '亠',
+        radical: '⼇',
+    },
+    Equivalent {
+        original: '冂',
+        unified: '冂',
+        radical: '⼌',
+    },
+    Equivalent {
+        original: '儿',
+        unified: '儿',
+        radical: '⼉',
+    },
+    Equivalent {
+        original: '勹',
+        unified: '勹',
+        radical: '⼓',
+    },
+    Equivalent {
+        original: '化',
+        unified: '亻',
+        radical: '⺅',
+    },
+    Equivalent {
+        original: '厶',
+        unified: '厶',
+        radical: '⼛',
+    },
+    Equivalent {
+        original: '个',
+        unified: '𠆢',
+        radical: '𠆢',
+    },
+    Equivalent {
+        original: '冖',
+        unified: '冖',
+        radical: '⼍',
+    },
+    Equivalent {
+        original: '人',
+        unified: '人',
+        radical: '⼈',
+    },
+    Equivalent {
+        original: '又',
+        unified: '又',
+        radical: '⼜',
+    },
+    Equivalent {
+        original: '匕',
+        unified: '匕',
+        radical: '⼔',
+    },
+    Equivalent {
+        original: '厂',
+        unified: '厂',
+        radical: '⼚',
+    },
+    Equivalent {
+        original: '乞',
+        unified: '𠂉',
+        radical: '𠂉',
+    },
+    Equivalent {
+        original: '卜',
+        unified: '卜',
+        radical: '⼘',
+    },
+    Equivalent {
+        original: '冫',
+        unified: '冫',
+        radical: '⼎',
+    },
+    Equivalent {
+        original: '凵',
+        unified: '凵',
+        radical: '⼐',
+    },
+    Equivalent {
+        original: '几',
+        unified: '几',
+        radical: '⼏',
+    },
+    Equivalent {
+        original: '刀',
+        unified: '刀',
+        radical: '⼑',
+    },
+    Equivalent {
+        original: '刈',
+        unified: '刂',
+        radical: '⺉',
+    },
+    Equivalent {
+        original: '匚',
+        unified: '匚',
+        radical: '⼕',
+    },
+    Equivalent {
+        original: '力',
+        unified: '力',
+        radical: '⼒',
+    },
+    Equivalent {
+        original: '卩',
+        unified: '卩',
+        radical: '⼙',
+    },
+    Equivalent {
+        original: 'マ',
+        unified: 'マ',
+        radical: 'マ',
+    },
+    Equivalent {
+        original: '九',
+        unified: '九',
+        radical: '九',
+    },
+    Equivalent {
+        original: '入',
+        unified: '入',
+        radical: '⼊',
+    },
+    Equivalent {
+        original: 'ユ',
+        unified: 'ユ',
+        radical: 'ユ',
+    },
+    Equivalent {
+        original: '乃',
+        unified: '乃',
+        radical: '乃',
+    },
+    Equivalent {
+        original: '口',
+        unified: '口',
+        radical: '⼝',
+    },
+    Equivalent {
+        original: '土',
+        unified: '土',
+        radical: '⼟',
+    },
+    Equivalent {
+        original: '艾',
+        unified: '艹',
+        radical: '⺾',
+    },
+    Equivalent {
+        original: '大',
+        unified: '大',
+        radical: '⼤',
+    },
+    Equivalent {
+        original: '小',
+        unified: '小',
+        radical: '⼩',
+    },
+    Equivalent {
+        original: '汁',
+        unified: '氵',
+        radical: '⺡',
+    },
+    Equivalent {
+        original: '幺',
+        unified: '幺',
+        radical: '⼳',
+    },
+    Equivalent {
+        original: '扎',
+        unified: '扌',
+        radical: '⺘',
+    },
+    Equivalent {
+        original: '宀',
+        unified: '宀',
+        radical: '⼧',
+    },
+    Equivalent {
+        original: '女',
+        unified: '女',
+        radical: '⼥',
+    },
+    Equivalent {
+        original: '山',
+        unified: '山',
+        radical: '⼭',
+    },
+    Equivalent {
+        original: 'ヨ',
+        unified: '彐',
+        radical: '⼹',
+    },
+    Equivalent {
+        original: '尸',
+        unified: '尸',
+        radical: '⼫',
+    },
+    Equivalent {
+        original: '廾',
+        unified: '廾',
+        radical: '⼶',
+    },
+    Equivalent {
+        original: '夂',
+        unified: '夂',
+        radical: '⼡',
+    },
+    Equivalent {
+        original: '忙',
+        unified: '忄',
+        radical: '⺖',
+    },
+    Equivalent {
+        original: '込',
+        unified: '辶',
+        radical: '⻌',
+    },
+    Equivalent {
+        original: '广',
+        unified: '广',
+        radical: '⼴',
+    },
+    Equivalent {
+        original: '寸',
+        unified: '寸',
+        radical: '⼨',
+    },
+    Equivalent {
+        original: '士',
+        unified: '士',
+        radical: '⼠',
+    },
+    Equivalent {
+        original: '囗',
+        unified: '囗',
+        radical: '⼞',
+    },
+    Equivalent {
+        original: '巾',
+        unified: '巾',
+        radical: '⼱',
+    },
+    Equivalent {
+        original: '彡',
+        unified: '彡',
+        radical: '⼺',
+    },
+    Equivalent {
+        original: '工',
+        unified: '工',
+        radical: '⼯',
+    },
+    Equivalent {
+        original: '尚',
+        unified: '𭕄',
+        radical: '⺌',
+    },
+    Equivalent {
+        original: '夕',
+        unified: '夕',
+        radical: '⼣',
+    },
+    Equivalent {
+        original: '弓',
+        unified: '弓',
+        radical: '⼸',
+    },
+    Equivalent {
+        original: '邦',
+        unified: '⻏',
+        radical: '⻏',
+    },
+    Equivalent {
+        original: '子',
+        unified: '子',
+        radical: '⼦',
+    },
+    Equivalent {
+        original: '干',
+        unified: '干',
+        radical: '⼲',
+    },
+    Equivalent {
+        original: '已',
+        unified: '已',
+        radical: '已',
+    },
+    Equivalent {
+        original: '阡',
+        unified: '阝',
+        radical: '⻖',
+    },
+    Equivalent {
+        original: '彳',
+        unified: '彳',
+        radical: '⼻',
+    },
+    Equivalent {
+        original: '犯',
+        unified: '犭',
+        radical: '⺨',
+    },
+    Equivalent {
+        original: '巛',
+        unified: '巛',
+        radical: '⼮',
+    },
+    Equivalent {
+        original: '弋',
+        unified: '弋',
+        radical: '⼷',
+    },
+    Equivalent {
+        original: '屮',
+        unified: '屮',
+        radical: '⼬',
+    },
+    Equivalent {
+        original: '廴',
+        unified: '廴',
+        radical: '⼵',
+    },
+    Equivalent {
+        original: '尢',
+        unified: '尢',
+        radical: '⼪',
+    },
+    Equivalent {
+        original: '川',
+        unified: '川',
+        radical: '川',
+    },
+    Equivalent {
+        original: '亡',
+        unified: '亡',
+        radical: '亡',
+    },
+    Equivalent {
+        original: '也',
+        unified: '也',
+        radical: '也',
+    },
+    Equivalent {
+        original: '彑',
+        unified: '彑',
+        radical: '⺔',
+    },
+    Equivalent {
+        original: '及',
+        unified: '及',
+        radical: '及',
+    },
+    Equivalent {
+        original: '久',
+        unified: '久',
+        radical: '久',
+    },
+    Equivalent {
+        original: '日',
+        unified: '日',
+        radical: '⽇',
+    },
+    Equivalent {
+        original: '木',
+        unified: '木',
+        radical: '⽊',
+    },
+    Equivalent {
+        original: '月',
+        unified: '月',
+        radical: '⽉',
+    },
+    Equivalent {
+        original: '杰',
+        unified: '灬',
+        radical: '⺣',
+    },
+    Equivalent {
+        original: '王',
+        unified: '王',
+        radical: '⺩',
+    },
+    Equivalent {
+        original: '止',
+        unified: '止',
+        radical: '⽌',
+    },
+    Equivalent {
+        original: '心',
+        unified: '心',
+        radical: '⼼',
+    },
+    Equivalent {
+        original: '戈',
+        unified: '戈',
+        radical: '⼽',
+    },
+    Equivalent {
+        original: '火',
+        unified: '火',
+        radical: '⽕',
+    },
+    Equivalent {
+        original: '攵',
+        unified: '攵',
+        radical: '⺙',
+    },
+    Equivalent {
+        original: '爪',
+        unified: '爪',
+        radical: '⽖',
+    },
+    Equivalent {
+        original: '曰',
+        unified: '曰',
+        radical: '⽈',
+    },
+    Equivalent {
+        original: '方',
+        unified: '方',
+        radical: '⽅',
+    },
+    Equivalent {
+        original: '水',
+        unified: '水',
+        radical: '⽔',
+    },
+    Equivalent {
+        original: '斤',
+        unified: '斤',
+        radical: '⽄',
+    },
+    Equivalent {
+        original: '比',
+        unified: '比',
+        radical: '⽐',
+    },
+    Equivalent {
+        original: '欠',
+        unified: '欠',
+        radical: '⽋',
+    },
+    Equivalent {
+        original: '殳',
+        unified: '殳',
+        radical: '⽎',
+    },
+    Equivalent {
+        original: '氏',
+        unified: '氏',
+        radical: '⽒',
+    },
+    Equivalent {
+        original: '牛',
+        unified: '牛',
+        radical: '⽜',
+    },
+    Equivalent {
+        original: '老',
+        unified: '耂',
+        radical: '⺹',
+    },
+    Equivalent {
+        original: '勿',
+        unified: '勿',
+        radical: '勿',
+    },
+    Equivalent {
+        original: '礼',
+        unified: '礻',
+        radical: '⺭',
+    },
+    Equivalent {
+        original: '爿',
+        unified: '爿',
+        radical: '⽙',
+    },
+    Equivalent {
+        original: '戸',
+        unified: '戸',
+        radical: '戸',
+    },
+    Equivalent {
+        original: '犬',
+        unified: '犬',
+        radical: '⽝',
+    },
+    Equivalent {
+        original: '歹',
+        unified: '歹',
+        radical: '⽍',
+    },
+    Equivalent {
+        original: '支',
+        unified: '支',
+        radical: '⽀',
+    },
+    Equivalent {
+        original: '巴',
+        unified: '巴',
+        radical: '巴',
+    },
+    Equivalent {
+        original: '手',
+        unified: '手',
+        radical: '⼿',
+    },
+    Equivalent {
+        original: '元',
+        unified: '元',
+        radical: '元',
+    },
+    Equivalent {
+        original: '父',
+        unified: '父',
+        radical: '⽗',
+    },
+    Equivalent {
+        original: '爻',
+        unified: '爻',
+        radical: '⽘',
+    },
+    Equivalent {
+        original: '文',
+        unified: '文',
+        radical: '⽂',
+    },
+    Equivalent {
+        original: '毋',
+        unified: '毋',
+        radical: '⽏',
+    },
+    Equivalent {
+        original: '片',
+        unified: '片',
+        radical: '⽚',
+    },
+    Equivalent {
+        original: '斗',
+        unified: '斗',
+        radical: '⽃',
+    },
+    Equivalent {
+        original: '五',
+        unified: '五',
+        radical: '五',
+    },
+    Equivalent {
+        original: '屯',
+        unified: '屯',
+        radical: '屯',
+    },
+    Equivalent {
+        original: '井',
+        unified: '井',
+        radical: '井',
+    },
+    Equivalent {
+        original: '无',
+        unified: '无',
+        radical: '⽆',
+    },
+    Equivalent {
+        original: '尤',
+        unified: '尤',
+        radical: '尤',
+    },
+    Equivalent {
+        original: '毛',
+        unified: '毛',
+        radical: '⽑',
+    },
+    Equivalent {
+        original: '气',
+        unified: '气',
+        radical: '⽓',
+    },
+    Equivalent {
+        original: '田',
+        unified: '田',
+        radical: '⽥',
+    },
+    Equivalent {
+        original: '目',
+        unified: '目',
+        radical: '⽬',
+    },
+    Equivalent {
+        original: '立',
+        unified: '立',
+        radical: '⽴',
+    },
+    Equivalent {
+        original: '禾',
+        unified: '禾',
+        radical: '⽲',
+    },
+    Equivalent {
+        original: '買',
+        unified: '罒',
+        radical: '⺲',
+    },
+    Equivalent {
+        original: '白',
+        unified: '白',
+        radical: '⽩',
+    },
+    Equivalent {
+        original: '石',
+        unified: '石',
+        radical: '⽯',
+    },
+    Equivalent {
+        original: '皿',
+        unified: '皿',
+        radical: '⽫',
+    },
+    Equivalent {
+        original: '疔',
+        unified: '疒',
+        radical: '⽧',
+    },
+    Equivalent {
+        original: '初',
+        unified: '衤',
+        radical: '⻂',
+    },
+    Equivalent {
+        original: '矢',
+        unified: '矢',
+        radical: '⽮',
+    },
+    Equivalent {
+        original: '示',
+        unified: '示',
+        radical: '⽰',
+    },
+    Equivalent {
+        original: '甘',
+        unified: '甘',
+        radical: '⽢',
+    },
+    Equivalent {
+        original: '疋',
+        unified: '疋',
+        radical: '⽦',
+    },
+    Equivalent {
+        original: '用',
+        unified: '用',
+        radical: '⽤',
+    },
+    Equivalent {
+        original: '矛',
+        unified: '矛',
+        radical: '⽭',
+    },
+    Equivalent {
+        original: '禹',
+        unified: '禸',
+        radical: '⽱',
+    },
+    Equivalent {
+        original: '冊',
+        unified: '冊',
+        radical: '冊',
+    },
+    Equivalent {
+        original: '穴',
+        unified: '穴',
+        radical: '⽳',
+    },
+    Equivalent {
+        original: '生',
+        unified: '生',
+        radical: '⽣',
+    },
+    Equivalent {
+        original: '玄',
+        unified: '玄',
+        radical: '⽞',
+    },
+    Equivalent {
+        original: '世',
+        unified: '世',
+        radical: '世',
+    },
+    Equivalent {
+        original: '母',
+        unified: '母',
+        radical: '⺟',
+    },
+    Equivalent {
+        original: '癶',
+        unified: '癶',
+        radical: '⽨',
+    },
+    Equivalent {
+        original: '牙',
+        unified: '牙',
+        radical: '⽛',
+    },
+    Equivalent {
+        original: '皮',
+        unified: '皮',
+        radical: '⽪',
+    },
+    Equivalent {
+        original: '瓦',
+        unified: '瓦',
+        radical: '⽡',
+    },
+    Equivalent {
+        original: '巨',
+        unified: '巨',
+        radical: '巨',
+    },
+    Equivalent {
+        original: '虫',
+        unified: '虫',
+        radical: '⾍',
+    },
+    Equivalent {
+        original: '糸',
+        unified: '糸',
+        radical: '⽷',
+    },
+    Equivalent {
+        original: '竹',
+        unified: '竹',
+        radical: '⽵',
+    },
+    Equivalent {
+        original: '米',
+        unified: '米',
+        radical: '⽶',
+    },
+    Equivalent {
+        original: '衣',
+        unified: '衣',
+        radical: '⾐',
+    },
+    Equivalent {
+        original: '耳',
+        unified: '耳',
+        radical: '⽿',
+    },
+    Equivalent {
+        original: '臼',
+        unified: '臼',
+        radical: '⾅',
+    },
+    Equivalent {
+        original: '羽',
+        unified: '羽',
+        radical: '⽻',
+    },
+    Equivalent {
+        original: '羊',
+        unified: '羊',
+        radical: '⽺',
+    },
+    Equivalent {
+        original: '虍',
+        unified: '虍',
+        radical: '⾌',
+    },
+    Equivalent {
+        original: '西',
+        unified: '西',
+        radical: '⻄',
+    },
+    Equivalent {
+        original: '艮',
+        unified: '艮',
+        radical: '⾉',
+    },
+    Equivalent {
+        original: '自',
+        unified: '自',
+        radical: '⾃',
+    },
+    Equivalent {
+        original: '舟',
+        unified: '舟',
+        radical: '⾈',
+    },
+    Equivalent {
+        original: '而',
+        unified: '而',
+        radical: '⽽',
+    },
+    Equivalent {
+        original: '聿',
+        unified: '聿',
+        radical: '⾀',
+    },
+    Equivalent {
+        original: '缶',
+        unified: '缶',
+        radical: '⽸',
+    },
+    Equivalent {
+        original: '至',
+        unified: '至',
+        radical: '⾄',
+    },
+    Equivalent {
+        original: '行',
+        unified: '行',
+        radical: '⾏',
+    },
+    Equivalent {
+        original: '舌',
+        unified: '舌',
+        radical: '⾆',
+    },
+    Equivalent {
+        original: '耒',
+        unified: '耒',
+        radical: '⽾',
+    },
+    Equivalent {
+        original: '瓜',
+        unified: '瓜',
+        radical: '⽠',
+    },
+    Equivalent {
+        original: '血',
+        unified: '血',
+        radical: '⾎',
+    },
+    Equivalent {
+        original: '色',
+        unified: '色',
+        radical: '⾊',
+    },
+    Equivalent {
+        original: '肉',
+        unified: '肉',
+        radical: '⾁',
+    },
+    Equivalent {
+        original: '貝',
+        unified: '貝',
+        radical: '⾙',
+    },
+    Equivalent {
+        original: '言',
+        unified: '言',
+        radical: '⾔',
+    },
+    Equivalent {
+        original: '車',
+        unified: '車',
+        radical: '⾞',
+    },
+    Equivalent {
+        original: '足',
+        unified: '足',
+        radical: '⾜',
+    },
+    Equivalent {
+        original: '豆',
+        unified: '豆',
+        radical: '⾖',
+    },
+    Equivalent {
+        original: '酉',
+        unified: '酉',
+        radical: '⾣',
+    },
+    Equivalent {
+        original: '豕',
+        unified: '豕',
+        radical: '⾗',
+    },
+    Equivalent {
+        original: '里',
+        unified: '里',
+        radical: '⾥',
+    },
+    Equivalent {
+        original: '辛',
+        unified: '辛',
+        radical: '⾟',
+    },
+    Equivalent {
+        original: '見',
+        unified: '見',
+        radical: '⾒',
+    },
+    Equivalent {
+        original: '臣',
+        unified: '臣',
+        radical: '⾂',
+    },
+    Equivalent {
+        original: '角',
+        unified: '角',
+        radical: '⾓',
+    },
+    Equivalent {
+        original: '走',
+        unified: '走',
+        radical: '⾛',
+    },
+    Equivalent {
+        original: '辰',
+        unified: '辰',
+        radical: '⾠',
+    },
+    Equivalent {
+        original: '舛',
+        unified: '舛',
+        radical: '⾇',
+    },
+    Equivalent {
+        original: '谷',
+        unified: '谷',
+        radical: '⾕',
+    },
+    Equivalent {
+        original: '釆',
+        unified: '釆',
+        radical: '⾤',
+    },
+    Equivalent {
+        original: '豸',
+        unified: '豸',
+        radical: '⾘',
+    },
+    Equivalent {
+        original: '身',
+        unified: '身',
+        radical: '⾝',
+    },
+    Equivalent {
+        original: '赤',
+        unified: '赤',
+        radical: '⾚',
+    },
+    Equivalent {
+        original: '麦',
+        unified: '麦',
+        radical: '⻨',
+    },
+    Equivalent {
+        original: '金',
+        unified: '金',
+        radical: '⾦',
+    },
+    Equivalent {
+        original: '隹',
+        unified: '隹',
+        radical: '⾫',
+    },
+    Equivalent {
+        original: '門',
+        unified: '門',
+        radical: '⾨',
+    },
+    Equivalent {
+        original: '雨',
+        unified: '雨',
+        radical: '⾬',
+    },
+    Equivalent {
+        original: '長',
+        unified: '長',
+        radical: '⾧',
+    },
+    Equivalent {
+        original: '非',
+        unified: '非',
+        radical: '⾮',
+    },
+    Equivalent {
+        original: '青',
+        unified: '青',
+        radical: '⻘',
+    },
+    Equivalent {
+        original: '免',
+        unified: '免',
+        radical: '免',
+    },
+    Equivalent {
+        original: '隶',
+        unified: '隶',
+        radical: '⾪',
+    },
+    Equivalent {
+        original: '奄',
+        unified: '奄',
+        radical: '奄',
+    },
+    Equivalent {
+        original: '岡',
+        unified: '岡',
+        radical: '岡',
+    },
+    Equivalent {
+        original: '斉',
+        unified: '斉',
+        radical: '⻫',
+    },
+    Equivalent {
+        original: '頁',
+        unified: '頁',
+        radical: '⾴',
+    },
+    Equivalent {
+        original: '革',
+        unified: '革',
+        radical: '⾰',
+    },
+    Equivalent {
+        original: '音',
+        unified: '音',
+        radical: '⾳',
+    },
+    Equivalent {
+        original: '食',
+        unified: '食',
+        radical: '⾷',
+    },
+    Equivalent {
+        original: '風',
+        unified: '風',
+        radical: '⾵',
+    },
+    Equivalent {
+        original: '品',
+        unified: '品',
+        radical: '品',
+    },
+    Equivalent {
+        original: '韭',
+        unified: '韭',
+        radical: '⾲',
+    },
+    Equivalent {
+        original: '面',
+        unified: '面',
+        radical: '⾯',
+    },
+    Equivalent {
+        original: '首',
+        unified: '首',
+        radical: '⾸',
+    },
+    Equivalent {
+        original: '香',
+        unified: '香',
+        radical: '⾹',
+    },
+    Equivalent {
+        original: '飛',
+        unified: '飛',
+        radical: '⾶',
+    },
+    Equivalent {
+        original: '馬',
+        unified: '馬',
+        radical: '⾺',
+    },
+    Equivalent {
+        original: '髟',
+        unified: '髟',
+        radical: '⾽',
+    },
+    Equivalent {
+        original: '骨',
+        unified: '骨',
+        radical: '⾻',
+    },
+    Equivalent {
+        original: '鬼',
+        unified: '鬼',
+        radical: '⿁',
+    },
+    Equivalent {
+        original: '韋',
+        unified: '韋',
+        radical: '⾱',
+    },
+    Equivalent {
+        original: '高',
+        unified: '高',
+        radical: '⾼',
+    },
+    Equivalent {
+        original: '鬲',
+        unified: '鬲',
+        radical: '⿀',
+    },
+    Equivalent {
+        original: '竜',
+        unified: '竜',
+        radical: '⻯',
+    },
+    Equivalent {
+        original: '鬥',
+        unified: '鬥',
+        radical: '⾾',
+    },
+    Equivalent {
+        original: '鬯',
+        unified: '鬯',
+        radical: '⾿',
+    },
+    Equivalent {
+        original: '魚',
+        unified: '魚',
+        radical: '⿂',
+    },
+    Equivalent {
+        original: '鳥',
+        unified: '鳥',
+        radical: '⿃',
+    },
+    Equivalent {
+        original: '鹿',
+        unified: '鹿',
+        radical: '⿅',
+    },
+    Equivalent {
+        original: '黒',
+        unified: '黒',
+        radical: '黒',
+    },
+    Equivalent {
+        original: '黄',
+        unified: '黄',
+        radical: '⻩',
+    },
+    Equivalent {
+        original: '麻',
+        unified: '麻',
+        radical: '⿇',
+    },
+    Equivalent {
+        original: '滴',
+        unified: '啇',
+        radical: '啇',
+    },
+    Equivalent {
+        original: '亀',
+        unified: '亀',
+        radical: '⻲',
+    },
+    Equivalent {
+        original: '鹵',
+        unified: '鹵',
+        radical: '⿄',
+    },
+    Equivalent {
+        original: '歯',
+        unified: '歯',
+        radical: '⻭',
+    },
+    Equivalent {
+        original: '無',
+        unified: '無',
+        radical: '無',
+    },
+    Equivalent {
+        original: '黍',
+        unified: '黍',
+        radical: '⿉',
+    },
+    Equivalent {
+        original: '黹',
+        unified: '黹',
+        radical: '⿋',
+    },
+    Equivalent {
+        original: '鼠',
+        unified: '鼠',
+        radical: '⿏',
+    },
+    Equivalent {
+        original: '黽',
+        unified: '黽',
+        radical: '⿌',
+    },
+    Equivalent {
+        original: '鼓',
+        unified: '鼓',
+        radical: '⿎',
+    },
+    Equivalent {
+        original: '鼎',
+        unified: '鼎',
+        radical: '⿍',
+    },
+    Equivalent {
+        original: '齊',
+        unified: '齊',
+        radical: '⿑',
+    },
+    Equivalent {
+        original: '鼻',
+        unified: '鼻',
+        radical: '⿐',
+    },
+    Equivalent {
+        original: '龠',
+        unified: '龠',
+        radical: '⿕',
+    },
+];
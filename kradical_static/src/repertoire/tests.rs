@@ -0,0 +1,38 @@
+use super::*;
+use crate::MEMBERSHIPS;
+use std::collections::HashSet;
+
+#[test]
+fn covers_every_radical() {
+    for membership in MEMBERSHIPS {
+        assert!(
+            equivalent(membership.radical).is_some(),
+            "No equivalent for {}",
+            membership.radical
+        );
+    }
+}
+
+#[test]
+fn never_shares_characters() {
+    for repertoire in [
+        Repertoire::Original,
+        Repertoire::Unified,
+        Repertoire::Radicals,
+    ] {
+        let glyphs: HashSet<_> = EQUIVALENTS
+            .iter()
+            .map(|equivalent| equivalent.get(repertoire))
+            .collect();
+        assert_eq!(glyphs.len(), EQUIVALENTS.len());
+    }
+}
+
+#[test]
+fn normalizes_between_repertoires() {
+    assert_eq!(normalize('⺅', Repertoire::Unified), '亻');
+    assert_eq!(normalize('亻', Repertoire::Radicals), '⺅');
+    assert_eq!(normalize('⺅', Repertoire::Original), '化');
+    assert_eq!(normalize('化', Repertoire::Unified), '亻');
+    assert_eq!(normalize('あ', Repertoire::Radicals), 'あ');
+}